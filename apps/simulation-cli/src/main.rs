@@ -0,0 +1,216 @@
+//! Headless runner, for training on machines without a browser:
+//!
+//! ```text
+//! $ shorelark --config config.toml --generations 1000 --seed 123 \
+//!             --checkpoints checkpoints --checkpoint-every 50
+//! ```
+//!
+//! With `--history stats.csv` (or `.json`), statistics of all the trained
+//! generations get written down for plotting.
+//!
+//! With `--islands`, a few simulations are trained in parallel, exchanging
+//! their best animals every `--migration-interval` generations.
+
+use anyhow::{bail, Context, Result};
+use lib_simulation as sim;
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "shorelark")]
+struct Args {
+    /// Path to the simulation's config (`.toml` or `.json`); it can contain
+    /// just some of the parameters, which then override the preset
+    #[structopt(short, long)]
+    config: Option<PathBuf>,
+
+    /// Preset to start from (default, large-world, fast-evolve or
+    /// predator-prey)
+    #[structopt(short, long, default_value = "default")]
+    preset: String,
+
+    /// Path to a checkpoint to resume training from; overrides `--config`
+    #[structopt(short, long)]
+    resume: Option<PathBuf>,
+
+    /// Number of generations to train
+    #[structopt(short, long, default_value = "100")]
+    generations: usize,
+
+    /// Seed for the random number generator; the same seed (and config) as
+    /// in the browser reproduces the exact same run
+    #[structopt(short, long, default_value = "0")]
+    seed: u64,
+
+    /// Directory where checkpoints should be written to
+    #[structopt(long)]
+    checkpoints: Option<PathBuf>,
+
+    /// How often (in generations) checkpoints should be written
+    #[structopt(long, default_value = "10")]
+    checkpoint_every: usize,
+
+    /// Path where statistics of all the trained generations should be
+    /// written to, as either CSV or JSON (depending on the file's extension)
+    #[structopt(long)]
+    history: Option<PathBuf>,
+
+    /// Number of islands (independent simulations) to train in parallel
+    #[structopt(long, default_value = "1")]
+    islands: usize,
+
+    /// How often (in generations) islands should exchange their champions
+    #[structopt(long, default_value = "10")]
+    migration_interval: usize,
+
+    /// Number of champions (per species) each island sends to the next one
+    #[structopt(long, default_value = "2")]
+    migrants: usize,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+
+    // Checkpoints don't contain statistics, so the history would be missing
+    // all the generations trained before the checkpoint was written
+    if args.resume.is_some() && args.history.is_some() {
+        bail!("--history is not supported together with --resume");
+    }
+
+    if args.islands > 1 {
+        return train_islands(&args);
+    }
+
+    let mut sim = if let Some(path) = &args.resume {
+        sim::Simulation::try_from_checkpoint(load(path)?, args.seed)?
+    } else {
+        sim::Simulation::try_new(config(&args)?, args.seed)?
+    };
+
+    if let Some(dir) = &args.checkpoints {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("couldn't create directory: {}", dir.display()))?;
+    }
+
+    for generation in 1..=args.generations {
+        let stats = sim.train();
+
+        println!("{}", stats);
+
+        if let Some(dir) = &args.checkpoints {
+            if generation % args.checkpoint_every.max(1) == 0 || generation == args.generations {
+                save(dir, &sim)?;
+            }
+        }
+    }
+
+    if let Some(path) = &args.history {
+        save_history(path, &sim)?;
+    }
+
+    Ok(())
+}
+
+fn train_islands(args: &Args) -> Result<()> {
+    if args.resume.is_some() {
+        bail!("--resume is not supported together with --islands");
+    }
+
+    if args.history.is_some() {
+        bail!("--history is not supported together with --islands");
+    }
+
+    let config = config(args)?;
+
+    let mut islands = sim::Islands::try_new(
+        vec![config; args.islands],
+        args.seed,
+        args.migration_interval.max(1),
+        args.migrants,
+    )?;
+
+    let dirs: Vec<_> = (0..args.islands)
+        .map(|id| {
+            args.checkpoints
+                .as_ref()
+                .map(|dir| dir.join(format!("island-{}", id)))
+        })
+        .collect();
+
+    for dir in dirs.iter().flatten() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("couldn't create directory: {}", dir.display()))?;
+    }
+
+    let mut generation = 0;
+
+    while generation < args.generations {
+        let generations = args
+            .migration_interval
+            .max(1)
+            .min(args.generations - generation);
+
+        for (id, stats) in islands.train_for(generations).into_iter().enumerate() {
+            for stats in stats {
+                println!("island {}: {}", id, stats);
+            }
+        }
+
+        generation += generations;
+
+        for (sim, dir) in islands.islands().zip(&dirs) {
+            if let Some(dir) = dir {
+                save(dir, sim)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn config(args: &Args) -> Result<sim::Config> {
+    let config = sim::Config::preset(&args.preset)
+        .with_context(|| format!("unknown preset: {}", args.preset))?;
+
+    match &args.config {
+        Some(path) => Ok(config.with_overrides(load(path)?)?),
+        None => Ok(config),
+    }
+}
+
+/// Loads a config or a checkpoint, as either TOML or JSON (depending on the
+/// file's extension).
+fn load<T>(path: &Path) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let file = fs::read_to_string(path)
+        .with_context(|| format!("couldn't read file: {}", path.display()))?;
+
+    let value = if path.extension().map_or(false, |ext| ext == "toml") {
+        toml::from_str(&file)?
+    } else {
+        serde_json::from_str(&file)?
+    };
+
+    Ok(value)
+}
+
+fn save(dir: &Path, sim: &sim::Simulation) -> Result<()> {
+    let path = dir.join(format!("checkpoint-{:06}.json", sim.generation()));
+    let checkpoint = serde_json::to_string(&sim.checkpoint())?;
+
+    fs::write(&path, checkpoint)
+        .with_context(|| format!("couldn't write checkpoint: {}", path.display()))
+}
+
+fn save_history(path: &Path, sim: &sim::Simulation) -> Result<()> {
+    let history = if path.extension().map_or(false, |ext| ext == "csv") {
+        sim.history().to_csv()
+    } else {
+        sim.history().to_json()
+    };
+
+    fs::write(path, history).with_context(|| format!("couldn't write history: {}", path.display()))
+}
@@ -1,45 +1,71 @@
+use crate::*;
 use std::iter::FromIterator;
 use std::ops::Index;
 
 #[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
 }
 
 #[allow(clippy::len_without_is_empty)] // chromosomes are not supposed to be empty, so it doesn't make much sense to have such function
-impl Chromosome {
+impl<G> Chromosome<G> {
     pub fn len(&self) -> usize {
         self.genes.len()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    crate fn as_slice(&self) -> &[G] {
+        &self.genes
+    }
+
+    crate fn as_mut_slice(&mut self) -> &mut [G] {
+        &mut self.genes
+    }
+
+    /// Root-mean-square distance between genes of both chromosomes.
+    pub fn distance(&self, other: &Self) -> f32
+    where
+        G: Gene,
+    {
+        assert_eq!(self.len(), other.len());
+
+        let sum: f32 = self
+            .genes
+            .iter()
+            .zip(&other.genes)
+            .map(|(a, b)| a.distance(b).powi(2))
+            .sum();
+
+        (sum / self.len() as f32).sqrt()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
 }
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
     }
 }
 
-impl FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
+impl<G> FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
         Self {
             genes: iter.into_iter().collect(),
         }
     }
 }
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = impl Iterator<Item = f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = impl Iterator<Item = G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
@@ -73,6 +99,20 @@ mod tests {
         }
     }
 
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let other = Chromosome {
+                genes: vec![1.0, 1.0, 0.0],
+            };
+
+            approx::assert_relative_eq!(chromosome().distance(&other), (8.0f32 / 3.0).sqrt());
+            approx::assert_relative_eq!(chromosome().distance(&chromosome()), 0.0);
+        }
+    }
+
     mod iter {
         use super::*;
 
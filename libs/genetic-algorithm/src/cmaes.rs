@@ -0,0 +1,274 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// Separable CMA-ES (Ros & Hansen, 2008): samples the population from a
+/// normal distribution and adapts its mean, step size and (diagonal)
+/// covariance towards the fittest samples.
+///
+/// The diagonal covariance makes it linear in the number of genes, which
+/// matters for neural networks with hundreds of weights.
+#[derive(Clone, Debug)]
+pub struct Cmaes {
+    sigma: f32,
+    state: Option<CmaesState>,
+}
+
+#[derive(Clone, Debug)]
+struct CmaesState {
+    mean: Vec<f32>,
+    variances: Vec<f32>,
+    sigma_path: Vec<f32>,
+    covariance_path: Vec<f32>,
+    generation: i32,
+}
+
+impl Cmaes {
+    /// Creates the optimizer; `sigma` is the initial step size. The
+    /// distribution's mean is initialized from the first population.
+    pub fn new(sigma: f32) -> Self {
+        assert!(sigma > 0.0);
+
+        Self { sigma, state: None }
+    }
+
+    pub fn sigma(&self) -> f32 {
+        self.sigma
+    }
+
+    pub fn mean(&self) -> Option<&[f32]> {
+        self.state.as_ref().map(|state| state.mean.as_slice())
+    }
+}
+
+impl<I> Optimizer<I> for Cmaes
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        let n = population[0].chromosome().len();
+        let params = Params::new(n, population.len());
+
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.fitness()
+                .partial_cmp(&a.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let parents = &ranked[..params.weights.len()];
+
+        match &mut self.state {
+            Some(state) => state.update(&params, &mut self.sigma, parents),
+
+            state @ None => {
+                let mean = (0..n)
+                    .map(|gene| {
+                        parents
+                            .iter()
+                            .zip(&params.weights)
+                            .map(|(parent, weight)| weight * parent.chromosome()[gene])
+                            .sum()
+                    })
+                    .collect();
+
+                *state = Some(CmaesState {
+                    mean,
+                    variances: vec![1.0; n],
+                    sigma_path: vec![0.0; n],
+                    covariance_path: vec![0.0; n],
+                    generation: 0,
+                });
+            }
+        }
+
+        let state = self.state.as_ref().unwrap();
+
+        let new_population = (0..population.len())
+            .map(|_| {
+                let child = state
+                    .mean
+                    .iter()
+                    .zip(&state.variances)
+                    .map(|(mean, variance)| mean + self.sigma * variance.sqrt() * gaussian(rng))
+                    .collect();
+
+                I::create(child)
+            })
+            .collect();
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+impl CmaesState {
+    fn update<I>(&mut self, params: &Params, sigma: &mut f32, parents: &[&I])
+    where
+        I: Individual,
+    {
+        let n = self.mean.len();
+
+        // Weighted step of the mean, in units of sigma
+        let step: Vec<f32> = (0..n)
+            .map(|gene| {
+                parents
+                    .iter()
+                    .zip(&params.weights)
+                    .map(|(parent, weight)| {
+                        weight * (parent.chromosome()[gene] - self.mean[gene]) / *sigma
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let old_mean = self.mean.clone();
+
+        for (mean, step) in self.mean.iter_mut().zip(&step) {
+            *mean += *sigma * step;
+        }
+
+        let cs = params.cs;
+        let cc = params.cc;
+
+        let paths = self.sigma_path.iter_mut().zip(&step).zip(&self.variances);
+
+        for ((path, step), variance) in paths {
+            *path = (1.0 - cs) * *path
+                + (cs * (2.0 - cs) * params.mu_eff).sqrt() * step / variance.sqrt();
+        }
+
+        self.generation += 1;
+
+        let sigma_path_norm = norm(&self.sigma_path);
+
+        let stalled = sigma_path_norm / (1.0 - (1.0 - cs).powi(2 * self.generation)).sqrt()
+            >= (1.4 + 2.0 / (n as f32 + 1.0)) * params.chi_n;
+
+        let h_sigma = if stalled { 0.0 } else { 1.0 };
+
+        for gene in 0..n {
+            self.covariance_path[gene] = (1.0 - cc) * self.covariance_path[gene]
+                + h_sigma * (cc * (2.0 - cc) * params.mu_eff).sqrt() * step[gene];
+
+            let rank_mu: f32 = parents
+                .iter()
+                .zip(&params.weights)
+                .map(|(parent, weight)| {
+                    weight * ((parent.chromosome()[gene] - old_mean[gene]) / *sigma).powi(2)
+                })
+                .sum();
+
+            let variance = self.variances[gene];
+
+            self.variances[gene] = (1.0 - params.c1 - params.cmu) * variance
+                + params.c1
+                    * (self.covariance_path[gene].powi(2)
+                        + (1.0 - h_sigma) * cc * (2.0 - cc) * variance)
+                + params.cmu * rank_mu;
+        }
+
+        *sigma *= ((cs / params.ds) * (sigma_path_norm / params.chi_n - 1.0)).exp();
+    }
+}
+
+/// Strategy parameters, as recommended by Hansen.
+struct Params {
+    weights: Vec<f32>,
+    mu_eff: f32,
+    cs: f32,
+    ds: f32,
+    cc: f32,
+    c1: f32,
+    cmu: f32,
+    chi_n: f32,
+}
+
+impl Params {
+    fn new(n: usize, lambda: usize) -> Self {
+        let n = n as f32;
+        let mu = (lambda / 2).max(1);
+
+        let weights: Vec<f32> = (1..=mu)
+            .map(|i| (mu as f32 + 0.5).ln() - (i as f32).ln())
+            .collect();
+
+        let weights_sum: f32 = weights.iter().sum();
+        let weights: Vec<f32> = weights.iter().map(|w| w / weights_sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f32>();
+
+        let cs = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let ds = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let cc = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+
+        // The separable variant can afford learning the (diagonal) covariance
+        // faster
+        let speedup = (n + 2.0) / 3.0;
+        let c1 = (speedup * 2.0 / ((n + 1.3).powi(2) + mu_eff)).min(1.0);
+
+        let cmu = (speedup * 2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff))
+            .max(0.0)
+            .min(1.0 - c1);
+
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            weights,
+            mu_eff,
+            cs,
+            ds,
+            cc,
+            c1,
+            cmu,
+            chi_n,
+        }
+    }
+}
+
+fn norm(vector: &[f32]) -> f32 {
+    vector.iter().map(|x| x * x).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Individual whose fitness peaks when all of its genes are 1.0.
+    struct Sphere(Chromosome);
+
+    impl Individual for Sphere {
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            -self.0.iter().map(|gene| (gene - 1.0).powi(2)).sum::<f32>()
+        }
+    }
+
+    #[test]
+    fn finds_the_optimum() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cmaes = Cmaes::new(0.5);
+
+        let mut population: Vec<_> = (0..12)
+            .map(|_| Sphere((0..4).map(|_| rng.gen_range(-2.0..2.0)).collect()))
+            .collect();
+
+        for _ in 0..100 {
+            population = cmaes.evolve(&mut rng, &population).0;
+        }
+
+        for gene in cmaes.mean().unwrap() {
+            approx::assert_relative_eq!(*gene, 1.0, epsilon = 0.01);
+        }
+
+        assert!(cmaes.sigma() < 0.1);
+    }
+}
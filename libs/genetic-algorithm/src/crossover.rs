@@ -1,14 +1,17 @@
-pub use self::uniform::*;
+pub use self::{order::*, partially_mapped::*, single_point::*, uniform::*};
 
 use crate::*;
 
+mod order;
+mod partially_mapped;
+mod single_point;
 mod uniform;
 
-pub trait CrossoverMethod {
+pub trait CrossoverMethod<G = f32> {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
 }
@@ -0,0 +1,69 @@
+use crate::*;
+
+/// Order crossover (OX), for permutations: copies a random segment of the
+/// first parent and fills the rest with the remaining genes, in the order
+/// they appear in the second parent.
+#[derive(Clone, Debug, Default)]
+pub struct OrderCrossover;
+
+impl<G> CrossoverMethod<G> for OrderCrossover
+where
+    G: Clone + PartialEq,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let (from, to) = segment(rng, parent_a.len());
+        let segment = &parent_a.as_slice()[from..to];
+
+        let mut rest = parent_b
+            .iter()
+            .filter(|gene| !segment.contains(gene))
+            .cloned();
+
+        (0..parent_a.len())
+            .map(|id| {
+                if (from..to).contains(&id) {
+                    parent_a[id].clone()
+                } else {
+                    rest.next()
+                        .expect("parents are not permutations of each other")
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns random `from..to` range within chromosome of given length.
+crate fn segment(rng: &mut dyn RngCore, len: usize) -> (usize, usize) {
+    let a = rng.gen_range(0..=len);
+    let b = rng.gen_range(0..=len);
+
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = (0..10).rev().collect();
+
+        let child = OrderCrossover::default().crossover(&mut rng, &parent_a, &parent_b);
+        let child: Vec<_> = child.into_iter().collect();
+
+        // Segment `1..9` comes from `parent_a`, the rest - in the order from
+        // `parent_b`
+        assert_eq!(child, vec![9, 1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    }
+}
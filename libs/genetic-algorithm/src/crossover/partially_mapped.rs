@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Partially mapped crossover (PMX), for permutations: copies a random
+/// segment of the first parent and fills the rest from the second parent,
+/// resolving duplicates through the mapping given by that segment.
+#[derive(Clone, Debug, Default)]
+pub struct PartiallyMappedCrossover;
+
+impl<G> CrossoverMethod<G> for PartiallyMappedCrossover
+where
+    G: Clone + PartialEq,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let (from, to) = segment(rng, parent_a.len());
+        let parent_a = parent_a.as_slice();
+        let parent_b = parent_b.as_slice();
+        let segment = &parent_a[from..to];
+
+        (0..parent_a.len())
+            .map(|id| {
+                if (from..to).contains(&id) {
+                    return parent_a[id].clone();
+                }
+
+                let mut gene = &parent_b[id];
+                let mut steps = 0;
+
+                // Gene is already present in the copied segment, so we have
+                // to follow the mapping until we find one that's not; for
+                // permutations that takes at most `segment.len()` steps,
+                // otherwise the mapping might loop forever
+                while let Some(pos) = segment.iter().position(|other| other == gene) {
+                    assert!(
+                        steps < segment.len(),
+                        "parents are not permutations of each other"
+                    );
+
+                    gene = &parent_b[from + pos];
+                    steps += 1;
+                }
+
+                gene.clone()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = vec![3, 7, 5, 1, 6, 8, 2, 4, 9, 0].into_iter().collect();
+
+        let child = PartiallyMappedCrossover::default().crossover(&mut rng, &parent_a, &parent_b);
+        let mut child: Vec<_> = child.into_iter().collect();
+
+        assert_eq!(child, vec![1, 9, 2, 3, 4, 5, 6, 7, 8, 0]);
+
+        // It's still a permutation
+        child.sort_unstable();
+        assert_eq!(child, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "parents are not permutations of each other")]
+    fn rejects_parents_that_arent_permutations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome<usize> = (0..10).collect();
+        let parent_b: Chromosome<usize> = vec![5; 10].into_iter().collect();
+
+        PartiallyMappedCrossover::default().crossover(&mut rng, &parent_a, &parent_b);
+    }
+}
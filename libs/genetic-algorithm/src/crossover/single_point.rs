@@ -0,0 +1,55 @@
+use crate::*;
+
+/// Cuts both parents at the same, random point and glues the beginning of
+/// the first one to the end of the second one.
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossover;
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+where
+    G: Clone,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let point = rng.gen_range(0..=parent_a.len());
+
+        parent_a
+            .iter()
+            .take(point)
+            .chain(parent_b.iter().skip(point))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[allow(clippy::float_cmp)] // it's safe, because we're comparing hard-coded floats only
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+
+        for _ in 0..10 {
+            let child = SinglePointCrossover::default().crossover(&mut rng, &parent_a, &parent_b);
+
+            // All the genes before the cut come from `parent_a`, and all the
+            // genes after it - from `parent_b`
+            let point = child.iter().take_while(|gene| **gene > 0.0).count();
+
+            assert!(child.iter().take(point).eq(parent_a.iter().take(point)));
+            assert!(child.iter().skip(point).eq(parent_b.iter().skip(point)));
+        }
+    }
+}
@@ -3,13 +3,16 @@ use crate::*;
 #[derive(Clone, Debug, Default)]
 pub struct UniformCrossover;
 
-impl CrossoverMethod for UniformCrossover {
+impl<G> CrossoverMethod<G> for UniformCrossover
+where
+    G: Clone,
+{
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
         assert_eq!(parent_a.len(), parent_b.len());
 
         let parent_a = parent_a.iter();
@@ -17,7 +20,8 @@ impl CrossoverMethod for UniformCrossover {
 
         parent_a
             .zip(parent_b)
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+            .map(|(a, b)| if rng.gen_bool(0.5) { a } else { b })
+            .cloned()
             .collect()
     }
 }
@@ -0,0 +1,140 @@
+use crate::*;
+use rand::seq::SliceRandom;
+use std::cmp::Ordering;
+
+/// (μ, λ) and (μ + λ) evolution strategies: the `mu` fittest individuals
+/// become parents, and each child is a random parent with Gaussian noise
+/// (of standard deviation `sigma`) added to all of its genes.
+///
+/// Population size (λ) is the size of the evaluated population.
+#[derive(Clone, Debug)]
+pub struct EvolutionStrategy {
+    mu: usize,
+    sigma: f32,
+    plus: bool,
+    parents: Vec<(Chromosome, f32)>,
+}
+
+impl EvolutionStrategy {
+    /// (μ, λ): parents are picked only among the children, so a good
+    /// individual can get lost, but the strategy doesn't get stuck on lucky
+    /// evaluations.
+    pub fn comma(mu: usize, sigma: f32) -> Self {
+        Self::new(mu, sigma, false)
+    }
+
+    /// (μ + λ): parents compete with their children (using the fitness they
+    /// had when they were evaluated), so the best individual is never lost.
+    pub fn plus(mu: usize, sigma: f32) -> Self {
+        Self::new(mu, sigma, true)
+    }
+
+    fn new(mu: usize, sigma: f32, plus: bool) -> Self {
+        assert!(mu > 0);
+        assert!(sigma > 0.0);
+
+        Self {
+            mu,
+            sigma,
+            plus,
+            parents: Vec::new(),
+        }
+    }
+}
+
+impl<I> Optimizer<I> for EvolutionStrategy
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        let mut candidates: Vec<_> = population
+            .iter()
+            .map(|individual| (individual.chromosome().clone(), individual.fitness()))
+            .collect();
+
+        if self.plus {
+            candidates.append(&mut self.parents);
+        }
+
+        candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        candidates.truncate(self.mu);
+
+        let new_population = (0..population.len())
+            .map(|_| {
+                let (parent, _) = candidates.choose(rng).unwrap();
+
+                let child = parent
+                    .iter()
+                    .map(|gene| gene + self.sigma * gaussian(rng))
+                    .collect();
+
+                I::create(child)
+            })
+            .collect();
+
+        if self.plus {
+            self.parents = candidates;
+        }
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn avg_fitness(mut strategy: EvolutionStrategy) -> (f32, f32) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.0; 3].into_iter().collect()))
+            .collect();
+
+        let initial = Statistics::new(&population).avg_fitness();
+
+        for _ in 0..10 {
+            population = strategy.evolve(&mut rng, &population).0;
+        }
+
+        (initial, Statistics::new(&population).avg_fitness())
+    }
+
+    #[test]
+    fn comma() {
+        let (initial, last) = avg_fitness(EvolutionStrategy::comma(3, 0.5));
+
+        assert!(last > initial + 5.0, "{} vs {}", initial, last);
+    }
+
+    #[test]
+    fn plus() {
+        let (initial, last) = avg_fitness(EvolutionStrategy::plus(3, 0.5));
+
+        assert!(last > initial + 5.0, "{} vs {}", initial, last);
+    }
+
+    #[test]
+    fn plus_keeps_the_parents() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut strategy = EvolutionStrategy::plus(1, 0.1);
+
+        let best = TestIndividual::create(vec![100.0].into_iter().collect());
+        let population = strategy.evolve(&mut rng, &[best]).0;
+
+        // Children are worse than their parent, so they should all be bred
+        // from it again
+        let worse: Vec<_> = population
+            .iter()
+            .map(|_| TestIndividual::create(vec![0.0].into_iter().collect()))
+            .collect();
+
+        let population = strategy.evolve(&mut rng, &worse).0;
+
+        assert!(population[0].fitness() > 99.0);
+    }
+}
@@ -0,0 +1,43 @@
+/// Something a chromosome can be made of - e.g. a weight (`f32`), a bit
+/// (`bool`) or a city's index (`usize`).
+pub trait Gene: Clone {
+    /// How different both genes are (0.0 meaning they are the same); used to
+    /// group similar chromosomes together (see `Speciation`).
+    fn distance(&self, other: &Self) -> f32;
+}
+
+impl Gene for f32 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs()
+    }
+}
+
+impl Gene for f64 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs() as f32
+    }
+}
+
+impl Gene for bool {
+    fn distance(&self, other: &Self) -> f32 {
+        if self == other {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+macro_rules! integer_genes {
+    ($($ty:ty),*) => {
+        $(
+            impl Gene for $ty {
+                fn distance(&self, other: &Self) -> f32 {
+                    (*self as f32 - *other as f32).abs()
+                }
+            }
+        )*
+    };
+}
+
+integer_genes!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
@@ -1,11 +1,17 @@
 use crate::*;
 
-pub trait Individual {
-    fn create(chromosome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
+pub trait Individual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
     fn fitness(&self) -> f32;
 }
 
+/// Individual judged on many objectives at once (higher is better for each of
+/// them), see `Nsga2`.
+pub trait MultiObjectiveIndividual: Individual {
+    fn objectives(&self) -> &[f32];
+}
+
 #[cfg(test)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum TestIndividual {
@@ -40,3 +46,13 @@ impl Individual for TestIndividual {
         }
     }
 }
+
+#[cfg(test)]
+impl MultiObjectiveIndividual for TestIndividual {
+    fn objectives(&self) -> &[f32] {
+        match self {
+            Self::WithChromosome { chromosome } => chromosome.as_slice(),
+            Self::WithFitness { .. } => panic!("not supported for TestIndividual::WithFitness"),
+        }
+    }
+}
@@ -2,62 +2,184 @@
 #![feature(min_type_alias_impl_trait)]
 
 pub use self::{
-    chromosome::*, crossover::*, individual::*, mutation::*, selection::*, statistics::*,
+    chromosome::*, cmaes::*, crossover::*, evolution_strategy::*, gene::*, individual::*,
+    map_elites::*, mutation::*, nsga2::*, optimizer::*, selection::*, speciation::*, statistics::*,
 };
 
-use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
 
 mod chromosome;
+mod cmaes;
 mod crossover;
+mod evolution_strategy;
+mod gene;
 mod individual;
+mod map_elites;
 mod mutation;
+mod nsga2;
+mod optimizer;
 mod selection;
+mod speciation;
 mod statistics;
 
-pub struct GeneticAlgorithm<S> {
-    selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+pub struct GeneticAlgorithm<G = f32> {
+    selection_method: Box<dyn SelectionMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    crossover_chance: f32,
+    mutation_methods: Vec<(f32, Box<dyn MutationMethod<G>>)>,
+    speciation: Option<Speciation>,
 }
 
-impl<S> GeneticAlgorithm<S>
+impl<G> GeneticAlgorithm<G>
 where
-    S: SelectionMethod,
+    G: Gene,
 {
     pub fn new(
-        selection_method: S,
-        crossover_method: impl CrossoverMethod + 'static,
-        mutation_method: impl MutationMethod + 'static,
+        selection_method: impl SelectionMethod + 'static,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
     ) -> Self {
         Self {
-            selection_method,
+            selection_method: Box::new(selection_method),
             crossover_method: Box::new(crossover_method),
-            mutation_method: Box::new(mutation_method),
+            crossover_chance: 1.0,
+            mutation_methods: vec![(1.0, Box::new(mutation_method))],
+            speciation: None,
         }
     }
 
+    /// Sets the chance of crossing parents over (1.0 by default); when
+    /// crossover doesn't happen, child starts as a copy of its first parent.
+    pub fn with_crossover_chance(mut self, chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        self.crossover_chance = chance;
+        self
+    }
+
+    /// Adds another mutation, applied to each child (with given chance) after
+    /// the previous ones.
+    pub fn with_mutation(
+        mut self,
+        chance: f32,
+        mutation_method: impl MutationMethod<G> + 'static,
+    ) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        self.mutation_methods
+            .push((chance, Box::new(mutation_method)));
+
+        self
+    }
+
+    /// Enables speciation, see `Speciation`.
+    pub fn with_speciation(mut self, speciation: Speciation) -> Self {
+        self.speciation = Some(speciation);
+        self
+    }
+
     pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
     where
-        I: Individual,
+        I: Individual<G>,
     {
         assert!(!population.is_empty());
 
-        let new_population = (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
+        if let Some(speciation) = &self.speciation {
+            return self.evolve_species(rng, population, speciation);
+        }
 
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+        let fitnesses: Vec<_> = population.iter().map(|i| i.fitness()).collect();
 
-                self.mutation_method.mutate(rng, &mut child);
+        let new_population = (0..population.len())
+            .map(|_| {
+                let parent_a =
+                    population[self.selection_method.select(rng, &fitnesses)].chromosome();
+                let parent_b =
+                    population[self.selection_method.select(rng, &fitnesses)].chromosome();
 
-                I::create(child)
+                I::create(self.breed(rng, parent_a, parent_b))
             })
             .collect();
 
         (new_population, Statistics::new(population))
     }
+
+    fn evolve_species<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        speciation: &Speciation,
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+    {
+        let species = speciation.speciate(population);
+        let offspring = speciation.offspring(population, &species);
+        let mut new_population = Vec::with_capacity(population.len());
+
+        for (members, offspring) in species.iter().zip(offspring) {
+            // Explicit fitness sharing
+            let fitnesses: Vec<_> = members
+                .iter()
+                .map(|&id| population[id].fitness() / members.len() as f32)
+                .collect();
+
+            for _ in 0..offspring {
+                let parent_a = members[self.selection_method.select(rng, &fitnesses)];
+                let parent_b = members[self.selection_method.select(rng, &fitnesses)];
+
+                let child = self.breed(
+                    rng,
+                    population[parent_a].chromosome(),
+                    population[parent_b].chromosome(),
+                );
+
+                new_population.push(I::create(child));
+            }
+        }
+
+        let mut statistics = Statistics::new(population);
+
+        statistics.species = species.len();
+
+        (new_population, statistics)
+    }
+
+    pub fn breed(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        let mut child = if happens(rng, self.crossover_chance) {
+            self.crossover_method.crossover(rng, parent_a, parent_b)
+        } else {
+            parent_a.clone()
+        };
+
+        for (chance, mutation_method) in &self.mutation_methods {
+            if happens(rng, *chance) {
+                mutation_method.mutate(rng, &mut child);
+            }
+        }
+
+        child
+    }
+}
+
+/// Rolls a dice, without touching the generator for certain events (so that
+/// the default, always-on operators yield the same results as before).
+fn happens(rng: &mut dyn RngCore, chance: f32) -> bool {
+    chance >= 1.0 || rng.gen_bool(chance as _)
+}
+
+/// Samples the standard normal distribution (through the Box-Muller
+/// transform).
+fn gaussian(rng: &mut dyn RngCore) -> f32 {
+    let a = 1.0 - rng.gen::<f32>();
+    let b = rng.gen::<f32>();
+
+    (-2.0 * a.ln()).sqrt() * (2.0 * std::f32::consts::PI * b).cos()
 }
 
 #[cfg(test)]
@@ -103,4 +225,114 @@ mod tests {
 
         assert_eq!(population, expected_population);
     }
+
+    #[test]
+    fn speciation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::default(),
+            UniformCrossover::default(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_speciation(Speciation::new(1.0));
+
+        let mut population = vec![
+            individual(&[1.0, 1.0]),
+            individual(&[1.0, 1.0]),
+            individual(&[1.0, 1.0]),
+            individual(&[5.0, 0.0]),
+        ];
+
+        let (new_population, statistics) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(statistics.species(), 2);
+
+        population = new_population;
+
+        // Thanks to the per-species offspring, neither of the species dies out
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        assert!(population.contains(&individual(&[1.0, 1.0])));
+        assert!(population.contains(&individual(&[5.0, 0.0])));
+    }
+
+    #[test]
+    fn binary_genes() {
+        #[derive(Debug)]
+        struct OneMax(Chromosome<bool>);
+
+        impl Individual<bool> for OneMax {
+            fn create(chromosome: Chromosome<bool>) -> Self {
+                Self(chromosome)
+            }
+
+            fn chromosome(&self) -> &Chromosome<bool> {
+                &self.0
+            }
+
+            fn fitness(&self) -> f32 {
+                self.0.iter().filter(|&&gene| gene).count() as f32
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2),
+            UniformCrossover::default(),
+            BitFlipMutation::new(0.01),
+        );
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| OneMax((0..20).map(|_| rng.gen_bool(0.5)).collect()))
+            .collect();
+
+        let initial = Statistics::new(&population).avg_fitness();
+
+        for _ in 0..20 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        assert!(Statistics::new(&population).avg_fitness() > initial + 5.0);
+    }
+
+    #[test]
+    fn crossover_chance() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::default(),
+            UniformCrossover::default(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_crossover_chance(0.0);
+
+        let population = vec![individual(&[1.0, 1.0]), individual(&[2.0, 2.0])];
+        let (children, _) = ga.evolve(&mut rng, &population);
+
+        assert!(children.iter().all(|child| population.contains(child)));
+    }
+
+    #[test]
+    fn mutation_pipeline() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::default(),
+            UniformCrossover::default(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+        .with_mutation(0.0, GaussianMutation::new(1.0, 10.0))
+        .with_mutation(1.0, GaussianMutation::new(1.0, 0.1));
+
+        let parent: Chromosome = vec![1.0, 1.0, 1.0].into_iter().collect();
+        let child = ga.breed(&mut rng, &parent, &parent);
+
+        // Only the last mutation should've been applied
+        assert_ne!(child, parent);
+        assert!(child.iter().all(|gene| (gene - 1.0).abs() <= 0.1));
+    }
 }
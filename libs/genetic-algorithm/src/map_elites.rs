@@ -0,0 +1,216 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+/// MAP-Elites: instead of evolving a single population, keeps an archive of
+/// the fittest individual found for each kind of behavior.
+///
+/// Behavior is described by a few numbers (e.g. how fast and how much an
+/// individual turns), each normalized into `0.0..=1.0`; each of these
+/// dimensions is split into a few cells, and each cell holds one elite.
+pub struct MapElites {
+    cells: Vec<usize>,
+    crossover_method: Box<dyn CrossoverMethod + Send>,
+    mutation_method: Box<dyn MutationMethod + Send>,
+    elites: BTreeMap<Vec<usize>, Elite>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Elite {
+    cell: Vec<usize>,
+    chromosome: Chromosome,
+    fitness: f32,
+    behavior: Vec<f32>,
+}
+
+impl MapElites {
+    /// Creates an empty archive; `cells` says into how many cells each of
+    /// the behavior dimensions is split.
+    pub fn new(
+        cells: Vec<usize>,
+        crossover_method: impl CrossoverMethod + Send + 'static,
+        mutation_method: impl MutationMethod + Send + 'static,
+    ) -> Self {
+        assert!(!cells.is_empty());
+        assert!(cells.iter().all(|&cells| cells > 0));
+
+        Self {
+            cells,
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            elites: Default::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elites.is_empty()
+    }
+
+    /// Fraction of the cells that already have an elite.
+    pub fn coverage(&self) -> f32 {
+        self.len() as f32 / self.cells.iter().product::<usize>() as f32
+    }
+
+    pub fn elites(&self) -> impl Iterator<Item = &Elite> {
+        self.elites.values()
+    }
+
+    pub fn elite(&self, cell: &[usize]) -> Option<&Elite> {
+        self.elites.get(cell)
+    }
+
+    /// Puts given individual into the archive, if it's fitter than the
+    /// current elite of its cell (or if that cell is empty); returns whether
+    /// the individual has become an elite.
+    pub fn insert<I>(&mut self, individual: &I, behavior: &[f32]) -> bool
+    where
+        I: Individual,
+    {
+        assert_eq!(behavior.len(), self.cells.len());
+
+        let cell: Vec<_> = behavior
+            .iter()
+            .zip(&self.cells)
+            .map(|(&value, &cells)| {
+                ((value.clamp(0.0, 1.0) * cells as f32) as usize).min(cells - 1)
+            })
+            .collect();
+
+        let is_better = self
+            .elites
+            .get(&cell)
+            .map_or(true, |elite| individual.fitness() > elite.fitness);
+
+        if is_better {
+            let elite = Elite {
+                cell: cell.clone(),
+                chromosome: individual.chromosome().clone(),
+                fitness: individual.fitness(),
+                behavior: behavior.to_vec(),
+            };
+
+            self.elites.insert(cell, elite);
+        }
+
+        is_better
+    }
+
+    /// Creates a new individual by crossing over (and mutating) two random
+    /// elites; returns `None` if the archive is empty.
+    pub fn breed<I>(&self, rng: &mut dyn RngCore) -> Option<I>
+    where
+        I: Individual,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let parent_a = self.random_elite(rng);
+        let parent_b = self.random_elite(rng);
+
+        let mut child =
+            self.crossover_method
+                .crossover(rng, &parent_a.chromosome, &parent_b.chromosome);
+
+        self.mutation_method.mutate(rng, &mut child);
+
+        Some(I::create(child))
+    }
+}
+
+impl MapElites {
+    fn random_elite(&self, rng: &mut dyn RngCore) -> &Elite {
+        let id = rng.gen_range(0..self.elites.len());
+
+        self.elites.values().nth(id).unwrap()
+    }
+}
+
+impl Elite {
+    pub fn cell(&self) -> &[usize] {
+        &self.cell
+    }
+
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    pub fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    pub fn behavior(&self) -> &[f32] {
+        &self.behavior
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    fn map_elites() -> MapElites {
+        MapElites::new(
+            vec![2, 4],
+            UniformCrossover::default(),
+            GaussianMutation::new(0.0, 0.0),
+        )
+    }
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn keeps_the_fittest_individual_per_cell() {
+            let mut map_elites = map_elites();
+
+            assert!(map_elites.insert(&individual(&[1.0]), &[0.1, 0.1]));
+            assert!(map_elites.insert(&individual(&[3.0]), &[0.2, 0.2]));
+            assert!(!map_elites.insert(&individual(&[2.0]), &[0.3, 0.0]));
+            assert!(map_elites.insert(&individual(&[2.0]), &[0.9, 1.0]));
+
+            assert_eq!(map_elites.len(), 2);
+            approx::assert_relative_eq!(map_elites.coverage(), 0.25);
+
+            let elites: Vec<_> = map_elites
+                .elites()
+                .map(|elite| (elite.cell().to_vec(), elite.fitness()))
+                .collect();
+
+            assert_eq!(elites, vec![(vec![0, 0], 3.0), (vec![1, 3], 2.0)]);
+        }
+    }
+
+    mod breed {
+        use super::*;
+
+        #[allow(clippy::float_cmp)] // it's safe, because we're comparing hard-coded floats only
+        #[test]
+        fn crosses_elites_over() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut map_elites = map_elites();
+
+            assert!(map_elites.breed::<TestIndividual>(&mut rng).is_none());
+
+            map_elites.insert(&individual(&[1.0, 1.0]), &[0.0, 0.0]);
+            map_elites.insert(&individual(&[2.0, 2.0]), &[1.0, 1.0]);
+
+            for _ in 0..10 {
+                let child: TestIndividual = map_elites.breed(&mut rng).unwrap();
+
+                assert!(child
+                    .chromosome()
+                    .iter()
+                    .all(|&gene| gene == 1.0 || gene == 2.0));
+            }
+        }
+    }
+}
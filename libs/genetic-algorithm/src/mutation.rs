@@ -1,9 +1,12 @@
-pub use self::gaussian::*;
+pub use self::{bit_flip::*, gaussian::*, inversion::*, swap::*};
 
 use crate::*;
 
+mod bit_flip;
 mod gaussian;
+mod inversion;
+mod swap;
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+pub trait MutationMethod<G = f32> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
 }
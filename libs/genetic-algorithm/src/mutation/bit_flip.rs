@@ -0,0 +1,58 @@
+use crate::*;
+
+/// Flips each bit with given chance.
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<bool> {
+        let mut child = vec![false; 100].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        BitFlipMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert!(actual(0.0).iter().all(|&gene| !gene));
+    }
+
+    #[test]
+    fn given_max_chance_flips_all_the_bits() {
+        assert!(actual(1.0).iter().all(|&gene| gene));
+    }
+
+    #[test]
+    fn given_fifty_fifty_chance_flips_roughly_half_of_the_bits() {
+        let flipped = actual(0.5).into_iter().filter(|&gene| gene).count();
+
+        assert_eq!(flipped, 51);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+/// With given chance, reverses order of genes in a random segment; keeps
+/// permutations valid.
+#[derive(Clone, Debug)]
+pub struct InversionMutation {
+    chance: f32,
+}
+
+impl InversionMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for InversionMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as _) {
+            return;
+        }
+
+        let a = rng.gen_range(0..child.len());
+        let b = rng.gen_range(0..child.len());
+
+        child.as_mut_slice()[a.min(b)..=a.max(b)].reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<usize> {
+        let mut child = (0..10).collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        InversionMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn given_max_chance_reverses_a_segment() {
+        assert_eq!(actual(1.0), vec![0, 1, 2, 3, 4, 5, 8, 7, 6, 9]);
+    }
+}
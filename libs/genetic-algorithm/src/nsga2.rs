@@ -0,0 +1,360 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// NSGA-II: evolves individuals that have many objectives (e.g. food eaten
+/// and energy left) without having to weight them against each other.
+///
+/// Each generation competes with its parents: both are sorted into Pareto
+/// fronts and the best fronts survive (with the last one that doesn't fit
+/// trimmed to its least crowded individuals), so that a non-dominated
+/// individual is never lost. Children are then bred from the survivors
+/// picked through binary tournaments, where an individual wins if it belongs
+/// to a better front or - when both are on the same front - if it's in a
+/// less crowded region of that front.
+pub struct Nsga2 {
+    crossover_method: Box<dyn CrossoverMethod + Send>,
+    mutation_method: Box<dyn MutationMethod + Send>,
+    parents: Vec<(Chromosome, Vec<f32>)>,
+}
+
+impl Nsga2 {
+    pub fn new(
+        crossover_method: impl CrossoverMethod + Send + 'static,
+        mutation_method: impl MutationMethod + Send + 'static,
+    ) -> Self {
+        Self {
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            parents: Vec::new(),
+        }
+    }
+}
+
+impl<I> Optimizer<I> for Nsga2
+where
+    I: MultiObjectiveIndividual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        assert!(!population.is_empty());
+
+        let mut candidates: Vec<_> = population
+            .iter()
+            .map(|individual| {
+                (
+                    individual.chromosome().clone(),
+                    individual.objectives().to_vec(),
+                )
+            })
+            .collect();
+
+        // Parents keep the objectives they had when they were evaluated
+        candidates.append(&mut self.parents);
+
+        let survivors = {
+            let objectives: Vec<_> = candidates
+                .iter()
+                .map(|(_, objectives)| objectives.as_slice())
+                .collect();
+
+            let mut survivors = Vec::with_capacity(population.len());
+
+            for (rank, front) in sort_into_fronts(&objectives).into_iter().enumerate() {
+                let mut front: Vec<_> = front
+                    .iter()
+                    .copied()
+                    .zip(crowding(&objectives, &front))
+                    .collect();
+
+                let free = population.len() - survivors.len();
+
+                if front.len() > free {
+                    front.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+                    front.truncate(free);
+                }
+
+                survivors.extend(front.into_iter().map(|(id, distance)| (id, rank, distance)));
+
+                if survivors.len() == population.len() {
+                    break;
+                }
+            }
+
+            survivors
+        };
+
+        let select = |rng: &mut dyn RngCore| {
+            let (a, a_rank, a_distance) = survivors[rng.gen_range(0..survivors.len())];
+            let (b, b_rank, b_distance) = survivors[rng.gen_range(0..survivors.len())];
+
+            let a_wins = a_rank < b_rank || (a_rank == b_rank && a_distance > b_distance);
+
+            if a_wins {
+                &candidates[a].0
+            } else {
+                &candidates[b].0
+            }
+        };
+
+        let new_population = (0..population.len())
+            .map(|_| {
+                let parent_a = select(rng);
+                let parent_b = select(rng);
+
+                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+
+                self.mutation_method.mutate(rng, &mut child);
+
+                I::create(child)
+            })
+            .collect();
+
+        self.parents = survivors
+            .iter()
+            .map(|&(id, _, _)| candidates[id].clone())
+            .collect();
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+/// Returns whether `a` is at least as good as `b` on all of the objectives and
+/// better on at least one of them (higher is better).
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits population into Pareto fronts, returning indices of individuals
+/// from each front; the first front contains individuals that are not
+/// dominated by anyone, the second one - individuals dominated only by the
+/// first front, and so on.
+pub fn non_dominated_sort<I>(population: &[I]) -> Vec<Vec<usize>>
+where
+    I: MultiObjectiveIndividual,
+{
+    sort_into_fronts(&objectives(population))
+}
+
+/// Returns how far each individual from given front is from its neighbours
+/// (summed over all of the objectives, each normalized by its range); the
+/// boundary individuals get an infinite distance, so that they are always
+/// preserved.
+pub fn crowding_distance<I>(population: &[I], front: &[usize]) -> Vec<f32>
+where
+    I: MultiObjectiveIndividual,
+{
+    crowding(&objectives(population), front)
+}
+
+fn objectives<I>(population: &[I]) -> Vec<&[f32]>
+where
+    I: MultiObjectiveIndividual,
+{
+    population
+        .iter()
+        .map(|individual| individual.objectives())
+        .collect()
+}
+
+fn sort_into_fronts(objectives: &[&[f32]]) -> Vec<Vec<usize>> {
+    let mut dominated = vec![Vec::new(); objectives.len()];
+    let mut dominators = vec![0; objectives.len()];
+
+    for (a, objectives_a) in objectives.iter().enumerate() {
+        for (b, objectives_b) in objectives.iter().enumerate() {
+            if dominates(objectives_a, objectives_b) {
+                dominated[a].push(b);
+                dominators[b] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+
+    let mut front: Vec<_> = (0..objectives.len())
+        .filter(|&id| dominators[id] == 0)
+        .collect();
+
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+
+        for &a in &front {
+            for &b in &dominated[a] {
+                dominators[b] -= 1;
+
+                if dominators[b] == 0 {
+                    next_front.push(b);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next_front;
+    }
+
+    fronts
+}
+
+fn crowding(objectives: &[&[f32]], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    let len = match front.first() {
+        Some(&id) => objectives[id].len(),
+        None => return distances,
+    };
+
+    for objective in 0..len {
+        let value = |id: usize| {
+            let point: &[f32] = objectives[front[id]];
+            point[objective]
+        };
+
+        let mut order: Vec<_> = (0..front.len()).collect();
+
+        order.sort_by(|&a, &b| value(a).partial_cmp(&value(b)).unwrap_or(Ordering::Equal));
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+        let range = value(last) - value(first);
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        if range > 0.0 {
+            for window in order.windows(3) {
+                distances[window[1]] += (value(window[2]) - value(window[0])) / range;
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(objectives: &[f32]) -> TestIndividual {
+        TestIndividual::create(objectives.iter().cloned().collect())
+    }
+
+    mod dominates {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert!(dominates(&[2.0, 1.0], &[1.0, 1.0]));
+            assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+            assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+        }
+    }
+
+    mod non_dominated_sort {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let population = vec![
+                individual(&[1.0, 1.0]),
+                individual(&[3.0, 1.0]),
+                individual(&[0.0, 0.0]),
+                individual(&[1.0, 3.0]),
+                individual(&[2.0, 2.0]),
+            ];
+
+            assert_eq!(
+                non_dominated_sort(&population),
+                vec![vec![1, 3, 4], vec![0], vec![2]]
+            );
+        }
+    }
+
+    mod crowding_distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let population = vec![
+                individual(&[0.0, 4.0]),
+                individual(&[1.0, 3.0]),
+                individual(&[3.0, 1.0]),
+                individual(&[4.0, 0.0]),
+            ];
+
+            let actual = crowding_distance(&population, &[0, 1, 2, 3]);
+
+            assert!(actual[0].is_infinite());
+            approx::assert_relative_eq!(actual[1], 1.5);
+            approx::assert_relative_eq!(actual[2], 1.5);
+            assert!(actual[3].is_infinite());
+        }
+    }
+
+    mod evolve {
+        use super::*;
+
+        #[test]
+        fn prefers_non_dominated_individuals() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut nsga2 =
+                Nsga2::new(UniformCrossover::default(), GaussianMutation::new(0.0, 0.0));
+
+            let mut population = vec![
+                individual(&[0.0, 0.0]),
+                individual(&[0.0, 0.0]),
+                individual(&[0.0, 0.0]),
+                individual(&[1.0, 3.0]),
+                individual(&[3.0, 1.0]),
+            ];
+
+            for _ in 0..10 {
+                population = nsga2.evolve(&mut rng, &population).0;
+            }
+
+            assert!(population
+                .iter()
+                .all(|individual| individual.objectives() != [0.0, 0.0]));
+        }
+
+        #[test]
+        fn keeps_non_dominated_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut nsga2 =
+                Nsga2::new(UniformCrossover::default(), GaussianMutation::new(0.0, 0.0));
+
+            nsga2.evolve(
+                &mut rng,
+                &[individual(&[5.0, 5.0]), individual(&[1.0, 6.0])],
+            );
+
+            // Children dominated by their parents should be bred from those
+            // parents again, instead of replacing them
+            let population = nsga2
+                .evolve(
+                    &mut rng,
+                    &[individual(&[0.0, 0.0]), individual(&[1.0, 1.0])],
+                )
+                .0;
+
+            assert_eq!(nsga2.parents.len(), 2);
+
+            for (chromosome, objectives) in &nsga2.parents {
+                assert_ne!(objectives.as_slice(), &[0.0, 0.0]);
+                assert_ne!(objectives.as_slice(), &[1.0, 1.0]);
+                assert_eq!(chromosome.as_slice(), objectives.as_slice());
+            }
+
+            for individual in &population {
+                let genes = individual.objectives();
+
+                assert!(genes[0] == 5.0 || genes[0] == 1.0);
+                assert!(genes[1] == 5.0 || genes[1] == 6.0);
+            }
+        }
+    }
+}
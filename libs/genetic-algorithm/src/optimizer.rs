@@ -0,0 +1,7 @@
+use crate::*;
+
+/// Algorithm that turns an evaluated population into the next one, keeping
+/// its own state between generations (e.g. `EvolutionStrategy` or `Cmaes`).
+pub trait Optimizer<I> {
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics);
+}
@@ -1,11 +1,12 @@
-pub use self::roulette_wheel::*;
+pub use self::{roulette_wheel::*, tournament::*};
 
 use crate::*;
 
 mod roulette_wheel;
+mod tournament;
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual;
+    /// Picks an individual, given fitnesses of the entire population; returns
+    /// index of the picked individual.
+    fn select(&self, rng: &mut dyn RngCore, fitnesses: &[f32]) -> usize;
 }
@@ -1,16 +1,14 @@
 use crate::*;
+use rand::distributions::{Distribution, WeightedIndex};
 
 #[derive(Clone, Debug, Default)]
 pub struct RouletteWheelSelection;
 
 impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual,
-    {
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
+    fn select(&self, rng: &mut dyn RngCore, fitnesses: &[f32]) -> usize {
+        WeightedIndex::new(fitnesses)
             .expect("got an empty population")
+            .sample(rng)
     }
 }
 
@@ -26,19 +24,15 @@ mod test {
         let method = RouletteWheelSelection::default();
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        let population = vec![
-            TestIndividual::new(2.0),
-            TestIndividual::new(1.0),
-            TestIndividual::new(4.0),
-            TestIndividual::new(3.0),
-        ];
-
-        let actual_histogram = (0..1000)
-            .map(|_| method.select(&mut rng, &population))
-            .fold(BTreeMap::default(), |mut histogram, individual| {
-                *histogram.entry(individual.fitness() as i32).or_default() += 1;
+        let fitnesses = [2.0, 1.0, 4.0, 3.0];
+
+        let actual_histogram = (0..1000).map(|_| method.select(&mut rng, &fitnesses)).fold(
+            BTreeMap::default(),
+            |mut histogram, id| {
+                *histogram.entry(fitnesses[id] as i32).or_default() += 1;
                 histogram
-            });
+            },
+        );
 
         let expected_histogram = maplit::btreemap! {
             // fitness => how many times this fitness has been chosen
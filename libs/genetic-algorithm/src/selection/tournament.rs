@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Picks a few random individuals and returns the fittest one; the larger
+/// the tournament, the stronger the selection pressure.
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select(&self, rng: &mut dyn RngCore, fitnesses: &[f32]) -> usize {
+        assert!(!fitnesses.is_empty(), "got an empty population");
+
+        let mut winner = rng.gen_range(0..fitnesses.len());
+
+        for _ in 1..self.size {
+            let candidate = rng.gen_range(0..fitnesses.len());
+
+            if fitnesses[candidate] > fitnesses[winner] {
+                winner = candidate;
+            }
+        }
+
+        winner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    fn histogram(size: usize) -> BTreeMap<i32, usize> {
+        let method = TournamentSelection::new(size);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let fitnesses = [2.0, 1.0, 4.0, 3.0];
+
+        (0..1000).map(|_| method.select(&mut rng, &fitnesses)).fold(
+            BTreeMap::default(),
+            |mut histogram, id| {
+                *histogram.entry(fitnesses[id] as i32).or_default() += 1;
+                histogram
+            },
+        )
+    }
+
+    #[test]
+    fn prefers_fitter_individuals() {
+        let histogram = histogram(2);
+
+        assert!(histogram[&1] < histogram[&2]);
+        assert!(histogram[&2] < histogram[&3]);
+        assert!(histogram[&3] < histogram[&4]);
+    }
+
+    #[test]
+    fn large_tournaments_pick_the_fittest_individual() {
+        let histogram = histogram(100);
+
+        assert_eq!(histogram.get(&4), Some(&1000));
+    }
+}
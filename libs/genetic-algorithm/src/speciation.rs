@@ -0,0 +1,156 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// Splits population into species of similar chromosomes, so that a few
+/// different strategies can evolve side by side instead of the population
+/// collapsing onto the first one that works.
+///
+/// Individuals share fitness with the other members of their species (i.e.
+/// their fitness is divided by the size of the species), and each species
+/// gets offspring proportionally to its total shared fitness; parents are
+/// then selected only within their own species.
+#[derive(Clone, Debug)]
+pub struct Speciation {
+    threshold: f32,
+}
+
+impl Speciation {
+    /// Creates speciation where individuals belong to the same species if
+    /// their chromosomes are closer than `threshold` (see
+    /// `Chromosome::distance()`).
+    pub fn new(threshold: f32) -> Self {
+        assert!(threshold > 0.0);
+
+        Self { threshold }
+    }
+
+    /// Returns indices of individuals that form each species; each species is
+    /// represented by its first member.
+    pub fn speciate<I, G>(&self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        I: Individual<G>,
+        G: Gene,
+    {
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for (id, individual) in population.iter().enumerate() {
+            let existing = species.iter_mut().find(|members| {
+                let representative = population[members[0]].chromosome();
+
+                individual.chromosome().distance(representative) < self.threshold
+            });
+
+            match existing {
+                Some(members) => members.push(id),
+                None => species.push(vec![id]),
+            }
+        }
+
+        species
+    }
+
+    /// Returns how many children each of given species should get, so that
+    /// there are `population` children in total.
+    crate fn offspring<I, G>(&self, individuals: &[I], species: &[Vec<usize>]) -> Vec<usize>
+    where
+        I: Individual<G>,
+    {
+        let population = individuals.len();
+
+        let shares: Vec<f32> = species
+            .iter()
+            .map(|members| {
+                let fitness: f32 = members
+                    .iter()
+                    .map(|&id| individuals[id].fitness().max(0.0))
+                    .sum();
+
+                fitness / members.len() as f32
+            })
+            .collect();
+
+        let total: f32 = shares.iter().sum();
+
+        let quotas: Vec<f32> = if total > 0.0 {
+            shares
+                .iter()
+                .map(|share| share / total * population as f32)
+                .collect()
+        } else {
+            species.iter().map(|members| members.len() as f32).collect()
+        };
+
+        let mut offspring: Vec<_> = quotas.iter().map(|quota| quota.floor() as usize).collect();
+
+        // Whatever's left after rounding down goes to the species with the
+        // largest remainders
+        let mut remainders: Vec<_> = (0..quotas.len()).collect();
+
+        remainders.sort_by(|&a, &b| {
+            let a = quotas[a] - quotas[a].floor();
+            let b = quotas[b] - quotas[b].floor();
+
+            b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+        });
+
+        let missing = population - offspring.iter().sum::<usize>();
+
+        for &id in remainders.iter().cycle().take(missing) {
+            offspring[id] += 1;
+        }
+
+        offspring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    mod speciate {
+        use super::*;
+
+        #[test]
+        fn groups_similar_chromosomes() {
+            let population = vec![
+                individual(&[0.0, 0.0]),
+                individual(&[5.0, 5.0]),
+                individual(&[0.1, 0.0]),
+                individual(&[5.0, 4.9]),
+                individual(&[-5.0, 0.0]),
+            ];
+
+            assert_eq!(
+                Speciation::new(1.0).speciate(&population),
+                vec![vec![0, 2], vec![1, 3], vec![4]]
+            );
+        }
+    }
+
+    mod offspring {
+        use super::*;
+
+        #[test]
+        fn is_proportional_to_shared_fitness() {
+            // Fitness of `TestIndividual` is the sum of its genes, so the
+            // first species has an average fitness of 1.0 and the second one
+            // - of 6.0
+            let population = vec![
+                individual(&[1.0, 0.0]),
+                individual(&[0.5, 0.5]),
+                individual(&[0.0, 1.0]),
+                individual(&[3.0, 3.0]),
+            ];
+
+            let speciation = Speciation::new(2.0);
+            let species = speciation.speciate(&population);
+
+            assert_eq!(species, vec![vec![0, 1, 2], vec![3]]);
+            assert_eq!(speciation.offspring(&population, &species), vec![1, 3]);
+        }
+    }
+}
@@ -7,12 +7,13 @@ pub struct Statistics {
     max_fitness: f32,
     avg_fitness: f32,
     median_fitness: f32,
+    crate species: usize,
 }
 
 impl Statistics {
-    crate fn new<I>(population: &[I]) -> Self
+    pub fn new<I, G>(population: &[I]) -> Self
     where
-        I: Individual,
+        I: Individual<G>,
     {
         assert!(!population.is_empty());
 
@@ -39,6 +40,7 @@ impl Statistics {
             max_fitness,
             avg_fitness,
             median_fitness,
+            species: 1,
         }
     }
 
@@ -57,6 +59,12 @@ impl Statistics {
     pub fn median_fitness(&self) -> f32 {
         self.median_fitness
     }
+
+    /// Number of species the population was split into (always 1 without
+    /// `Speciation`).
+    pub fn species(&self) -> usize {
+        self.species
+    }
 }
 
 #[cfg(test)]
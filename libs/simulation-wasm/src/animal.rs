@@ -2,19 +2,46 @@ use crate::*;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Animal {
+    pub species: sim::Species,
     pub x: f32,
     pub y: f32,
     pub rotation: f32,
     pub vision: Vec<f32>,
+    pub energy: f32,
+    pub alive: bool,
+    pub lifetime: usize,
+    pub satiation: usize,
+    pub distance: f32,
+    pub average_speed: f32,
+    pub turning: f32,
+    pub food_in_view: usize,
+
+    /// Number of meals eaten so far - timestamps of all of them are
+    /// available through `Simulation::meals()`
+    pub meals: usize,
+
+    /// Step (of the animal's life) at which it ate most recently
+    pub last_meal: Option<usize>,
 }
 
 impl From<&sim::Animal> for Animal {
     fn from(animal: &sim::Animal) -> Self {
         Self {
+            species: animal.species(),
             x: animal.position().x,
             y: animal.position().y,
             rotation: animal.rotation().angle(),
             vision: animal.vision().to_owned(),
+            energy: animal.energy(),
+            alive: animal.is_alive(),
+            lifetime: animal.lifetime(),
+            satiation: animal.satiation(),
+            distance: animal.distance(),
+            average_speed: animal.average_speed(),
+            turning: animal.turning(),
+            food_in_view: animal.food_in_view(),
+            meals: animal.meals().len(),
+            last_meal: animal.meals().last().copied(),
         }
     }
 }
@@ -4,6 +4,9 @@ use crate::*;
 pub struct Food {
     pub x: f32,
     pub y: f32,
+    pub kind: usize,
+    pub size: f32,
+    pub nutrition: f32,
 }
 
 impl From<&sim::Food> for Food {
@@ -11,6 +14,9 @@ impl From<&sim::Food> for Food {
         Self {
             x: food.position().x,
             y: food.position().y,
+            kind: food.kind(),
+            size: food.size(),
+            nutrition: food.nutrition(),
         }
     }
 }
@@ -1,7 +1,8 @@
-pub use self::{animal::*, food::*, world::*};
+pub use self::{animal::*, food::*, statistics::*, world::*};
 
 mod animal;
 mod food;
+mod statistics;
 mod world;
 
 use lib_simulation as sim;
@@ -11,26 +12,64 @@ use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct Simulation {
-    rng: ThreadRng,
     sim: sim::Simulation,
 }
 
 #[wasm_bindgen]
 impl Simulation {
+    /// Creates a new simulation; when `seed` is omitted, a random one is
+    /// picked (it can be later read through `.seed()`).
+    ///
+    /// Throws if the config is invalid.
     #[wasm_bindgen(constructor)]
-    pub fn new(config: JsValue) -> Self {
-        let config: sim::Config = config.into_serde().unwrap();
+    pub fn new(config: JsValue, seed: Option<u32>) -> Result<Simulation, JsValue> {
+        Ok(Self {
+            sim: Self::simulation(config, seed)?,
+        })
+    }
 
-        let mut rng = thread_rng();
-        let sim = sim::Simulation::random(config, &mut rng);
+    /// Starts the simulation from scratch, with given config and seed.
+    ///
+    /// Throws if the config is invalid, leaving the current simulation
+    /// intact.
+    pub fn reset(&mut self, config: JsValue, seed: Option<u32>) -> Result<(), JsValue> {
+        self.sim = Self::simulation(config, seed)?;
+        Ok(())
+    }
 
-        Self { rng, sim }
+    /// Seed this simulation was started with - passing it (along with the
+    /// same config) to the native simulation reproduces the exact same run.
+    pub fn seed(&self) -> u32 {
+        self.sim.seed() as u32
     }
 
     pub fn default_config() -> JsValue {
         JsValue::from_serde(&sim::Config::default()).unwrap()
     }
 
+    /// Names of all the available presets.
+    pub fn presets() -> JsValue {
+        JsValue::from_serde(sim::Config::PRESETS).unwrap()
+    }
+
+    /// Returns given preset with given (possibly partial, possibly
+    /// `undefined`) config merged over it.
+    ///
+    /// Throws if there's no such preset or the config has unknown fields.
+    pub fn preset_config(name: &str, overrides: JsValue) -> Result<JsValue, JsValue> {
+        let config = sim::Config::preset(name)
+            .ok_or_else(|| JsValue::from_str(&format!("unknown preset: {}", name)))?;
+
+        let config = if overrides.is_undefined() {
+            config
+        } else {
+            let overrides: serde_json::Value = overrides.into_serde().map_err(to_js_error)?;
+            config.with_overrides(overrides).map_err(to_js_error)?
+        };
+
+        Ok(JsValue::from_serde(&config).unwrap())
+    }
+
     pub fn config(&self) -> JsValue {
         JsValue::from_serde(self.sim.config()).unwrap()
     }
@@ -40,11 +79,56 @@ impl Simulation {
         JsValue::from_serde(&world).unwrap()
     }
 
-    pub fn step(&mut self) -> Option<String> {
-        self.sim.step(&mut self.rng).map(|stats| stats.to_string())
+    /// Steps (of the animal's life) at which given animal (index into
+    /// `world().animals`) ate; kept out of `.world()`, so that the world
+    /// doesn't grow with each meal.
+    pub fn meals(&self, animal: usize) -> Option<Vec<u32>> {
+        let animal = self.sim.world().animals().get(animal)?;
+
+        Some(animal.meals().iter().map(|&step| step as u32).collect())
+    }
+
+    /// Statistics of all the generations simulated so far, as an array of
+    /// `{ generation, ga, predators }` objects.
+    pub fn history(&self) -> JsValue {
+        JsValue::from_serde(self.sim.history()).unwrap()
+    }
+
+    pub fn history_csv(&self) -> String {
+        self.sim.history().to_csv()
+    }
+
+    pub fn history_json(&self) -> String {
+        self.sim.history().to_json()
+    }
+
+    /// Performs a single step; when it finishes a generation, returns its
+    /// statistics as a `{ generation, ga, predators, text }` object (with
+    /// `text` being their human-readable form).
+    pub fn step(&mut self) -> JsValue {
+        match self.sim.step() {
+            Some(stats) => JsValue::from_serde(&Statistics::from(stats)).unwrap(),
+            None => JsValue::undefined(),
+        }
+    }
+
+    /// Fast-forwards to the end of the current generation; returns the same
+    /// object as `.step()`.
+    pub fn train(&mut self) -> JsValue {
+        let stats = Statistics::from(self.sim.train());
+        JsValue::from_serde(&stats).unwrap()
     }
+}
+
+impl Simulation {
+    fn simulation(config: JsValue, seed: Option<u32>) -> Result<sim::Simulation, JsValue> {
+        let config: sim::Config = config.into_serde().map_err(to_js_error)?;
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
 
-    pub fn train(&mut self) -> String {
-        self.sim.train(&mut self.rng).to_string()
+        sim::Simulation::try_new(config, seed as u64).map_err(to_js_error)
     }
 }
+
+fn to_js_error(err: impl ToString) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
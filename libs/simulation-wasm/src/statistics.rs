@@ -0,0 +1,18 @@
+use crate::*;
+
+/// Statistics of a single generation, along with their human-readable form
+/// (as printed by the native simulation).
+#[derive(Clone, Debug, Serialize)]
+pub struct Statistics {
+    #[serde(flatten)]
+    pub stats: sim::Statistics,
+    pub text: String,
+}
+
+impl From<sim::Statistics> for Statistics {
+    fn from(stats: sim::Statistics) -> Self {
+        let text = stats.to_string();
+
+        Self { stats, text }
+    }
+}
@@ -9,7 +9,12 @@ pub struct World {
 impl From<&sim::World> for World {
     fn from(world: &sim::World) -> Self {
         let animals = world.animals().iter().map(Animal::from).collect();
-        let foods = world.foods().iter().map(Food::from).collect();
+        let foods = world
+            .foods()
+            .iter()
+            .filter(|food| food.is_available())
+            .map(Food::from)
+            .collect();
 
         Self { animals, foods }
     }
@@ -1,7 +1,11 @@
 use crate::*;
 
+/// How often (in steps) positions of animals get recorded into their trails.
+crate const TRAIL_INTERVAL: usize = 50;
+
 #[derive(Debug)]
 pub struct Animal {
+    crate species: Species,
     crate position: na::Point2<f32>,
     crate rotation: na::Rotation2<f32>,
     crate vision: Vec<f32>,
@@ -9,9 +13,23 @@ pub struct Animal {
     crate eye: Eye,
     crate brain: Brain,
     crate satiation: usize,
+    crate unspent_satiation: usize,
+    crate nutrition: f32,
+    crate energy: f32,
+    crate energy_spent: f32,
+    crate lifetime: usize,
+    crate distance: f32,
+    crate turning: f32,
+    crate food_in_view: usize,
+    crate meals: Vec<usize>,
+    crate trail: Vec<na::Point2<f32>>,
 }
 
 impl Animal {
+    pub fn species(&self) -> Species {
+        self.species
+    }
+
     pub fn position(&self) -> na::Point2<f32> {
         self.position
     }
@@ -23,55 +41,340 @@ impl Animal {
     pub fn vision(&self) -> &[f32] {
         &self.vision
     }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    /// Sum of nutrition of all the food eaten (negative for poison).
+    pub fn nutrition(&self) -> f32 {
+        self.nutrition
+    }
+
+    /// Energy burned on living, moving and turning.
+    pub fn energy_spent(&self) -> f32 {
+        self.energy_spent
+    }
+
+    /// Number of steps this animal has been alive for.
+    pub fn lifetime(&self) -> usize {
+        self.lifetime
+    }
+
+    /// Distance travelled.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Average distance travelled per step.
+    pub fn average_speed(&self) -> f32 {
+        self.distance / (self.lifetime.max(1) as f32)
+    }
+
+    /// Total rotation (in radians, regardless of the direction).
+    pub fn turning(&self) -> f32 {
+        self.turning
+    }
+
+    /// Number of food eaten (including poison); for predators - number of
+    /// prey caught.
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    /// Number of steps during which this animal has seen something to eat
+    /// (any kind of food for prey, prey for predators).
+    pub fn food_in_view(&self) -> usize {
+        self.food_in_view
+    }
+
+    /// Steps of this animal's lifetime at which it has eaten something, in
+    /// chronological order.
+    pub fn meals(&self) -> &[usize] {
+        &self.meals
+    }
+
+    /// Positions this animal has been at, starting with the initial one and
+    /// then recorded every few steps.
+    pub fn trail(&self) -> &[na::Point2<f32>] {
+        &self.trail
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.energy > 0.0
+    }
 }
 
 impl Animal {
-    crate fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
-        let brain = Brain::random(config, rng);
+    crate fn random(config: &Config, rng: &mut dyn RngCore, species: Species) -> Self {
+        let brain = Brain::random(config, rng, species);
 
-        Self::new(config, rng, brain)
+        Self::new(config, rng, species, brain)
     }
 
     crate fn from_chromosome(
         config: &Config,
         rng: &mut dyn RngCore,
+        species: Species,
         chromosome: ga::Chromosome,
     ) -> Self {
-        let brain = Brain::from_chromosome(config, chromosome);
+        let brain = Brain::from_chromosome(config, species, chromosome);
 
-        Self::new(config, rng, brain)
+        Self::new(config, rng, species, brain)
     }
 
     crate fn as_chromosome(&self) -> ga::Chromosome {
         self.brain.as_chromosome()
     }
 
-    crate fn process_brain(&mut self, config: &Config, foods: &[Food]) {
-        self.vision = self.eye.process_vision(self.position, self.rotation, foods);
+    crate fn eat(&mut self, config: &Config, nutrition: f32) {
+        self.satiation += 1;
+        self.unspent_satiation += 1;
+        self.nutrition += nutrition;
+        self.meals.push(self.lifetime);
+
+        self.energy = (self.energy + nutrition * config.energy_food).clamp(0.0, config.energy_max);
+
+        if !self.is_alive() {
+            self.die();
+        }
+    }
+
+    crate fn die(&mut self) {
+        self.energy = 0.0;
+        self.speed = 0.0;
+        self.vision.iter_mut().for_each(|cell| *cell = 0.0);
+    }
+
+    /// Looks around: prey sees each kind of food separately (and predators,
+    /// if there are any), while predators see prey.
+    crate fn process_vision(&self, config: &Config, world: &World) -> Vec<f32> {
+        if !self.is_alive() {
+            return vec![0.0; self.vision.len()];
+        }
+
+        let (position, rotation) = (self.position, self.rotation);
+
+        match self.species {
+            Species::Prey => {
+                let mut vision = Vec::with_capacity(self.vision.len());
+
+                for kind in 0..config.food_kinds.len() {
+                    let foods = world.food_positions(kind);
+                    vision.extend(self.eye.process_vision(position, rotation, foods));
+                }
+
+                if config.world_predators > 0 {
+                    let predators = world.positions(Species::Predator);
+                    vision.extend(self.eye.process_vision(position, rotation, predators));
+                }
+
+                vision
+            }
+
+            Species::Predator => {
+                let prey = world.positions(Species::Prey);
+                self.eye.process_vision(position, rotation, prey)
+            }
+        }
+    }
+
+    crate fn process_brain(&mut self, config: &Config, vision: Vec<f32>) {
+        if !self.is_alive() {
+            return;
+        }
+
+        self.vision = vision;
+
+        if self.sees_food(config) {
+            self.food_in_view += 1;
+        }
 
         let (speed, rotation) = self.brain.propagate(self.vision.clone());
 
         self.speed = (self.speed + speed).clamp(config.sim_speed_min, config.sim_speed_max);
         self.rotation = na::Rotation2::new(self.rotation.angle() + rotation);
+        self.turning += rotation.abs();
+
+        self.process_metabolism(config, rotation);
     }
 
     crate fn process_movement(&mut self) {
+        if !self.is_alive() {
+            return;
+        }
+
         self.position += self.rotation * na::Vector2::new(self.speed, 0.0);
+        self.distance += self.speed;
         self.position.x = na::wrap(self.position.x, 0.0, 1.0);
         self.position.y = na::wrap(self.position.y, 0.0, 1.0);
+
+        if self.lifetime % TRAIL_INTERVAL == 0 {
+            self.trail.push(self.position);
+        }
     }
 }
 
 impl Animal {
-    fn new(config: &Config, rng: &mut dyn RngCore, brain: Brain) -> Self {
+    fn new(config: &Config, rng: &mut dyn RngCore, species: Species, brain: Brain) -> Self {
+        let position = rng.gen();
+
         Self {
-            position: rng.gen(),
+            species,
+            position,
             rotation: rng.gen(),
-            vision: vec![0.0; config.eye_cells],
+            vision: vec![0.0; config.eye_cells * species.eye_channels(config)],
             speed: config.sim_speed_max,
             eye: Eye::new(config),
             brain,
             satiation: 0,
+            unspent_satiation: 0,
+            nutrition: 0.0,
+            energy: config.energy_initial,
+            energy_spent: 0.0,
+            lifetime: 0,
+            distance: 0.0,
+            turning: 0.0,
+            food_in_view: 0,
+            meals: Vec::new(),
+            trail: vec![position],
+        }
+    }
+
+    fn sees_food(&self, config: &Config) -> bool {
+        let cells = match self.species {
+            Species::Prey => config.food_kinds.len() * config.eye_cells,
+            Species::Predator => self.vision.len(),
+        };
+
+        self.vision[..cells].iter().any(|&cell| cell > 0.0)
+    }
+
+    /// Burns energy spent on staying alive, moving and turning during the
+    /// current step; animals that run out of energy stop in place.
+    fn process_metabolism(&mut self, config: &Config, rotation: f32) {
+        let speed = self.speed / config.sim_speed_max;
+        let rotation = rotation.abs() / config.sim_rotation_accel;
+
+        let cost = config.energy_cost_idle
+            + config.energy_cost_speed * speed
+            + config.energy_cost_rotation * rotation;
+
+        self.energy_spent += cost.min(self.energy);
+        self.energy = (self.energy - cost).max(0.0);
+        self.lifetime += 1;
+
+        if !self.is_alive() {
+            self.die();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animal(config: &Config) -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Animal::random(config, &mut rng, Species::Prey)
+    }
+
+    mod eat {
+        use super::*;
+
+        #[test]
+        fn restores_energy_up_to_the_limit() {
+            let config = Config::default();
+            let mut animal = animal(&config);
+
+            animal.eat(&config, 1.0);
+            approx::assert_relative_eq!(animal.energy(), 1.5);
+
+            animal.eat(&config, 1.0);
+            animal.eat(&config, 1.0);
+            approx::assert_relative_eq!(animal.energy(), 2.0);
+
+            assert_eq!(animal.satiation(), 3);
+            assert_eq!(animal.meals(), &[0, 0, 0]);
+        }
+
+        #[test]
+        fn poison_drains_energy() {
+            let config = Config::default();
+            let mut animal = animal(&config);
+
+            animal.eat(&config, -1.0);
+            approx::assert_relative_eq!(animal.energy(), 0.5);
+            approx::assert_relative_eq!(animal.nutrition, -1.0);
+
+            animal.eat(&config, -2.0);
+            assert!(!animal.is_alive());
+        }
+    }
+
+    mod process_brain {
+        use super::*;
+
+        #[test]
+        fn counts_steps_with_food_in_view() {
+            let config = Config {
+                world_predators: 1,
+                ..Default::default()
+            };
+
+            let mut animal = animal(&config);
+            let cells = config.food_kinds.len() * config.eye_cells;
+
+            animal.process_brain(&config, vec![0.0; animal.vision.len()]);
+            assert_eq!(animal.food_in_view(), 0);
+
+            // Seeing just a predator doesn't count
+            let mut vision = vec![0.0; animal.vision.len()];
+            vision[cells] = 1.0;
+            animal.process_brain(&config, vision);
+            assert_eq!(animal.food_in_view(), 0);
+
+            let mut vision = vec![0.0; animal.vision.len()];
+            vision[cells - 1] = 0.5;
+            animal.process_brain(&config, vision);
+            assert_eq!(animal.food_in_view(), 1);
+
+            assert_eq!(animal.lifetime(), 3);
+        }
+    }
+
+    mod process_metabolism {
+        use super::*;
+
+        #[test]
+        fn burns_energy_proportionally_to_speed_and_rotation() {
+            let config = Config::default();
+            let mut animal = animal(&config);
+
+            animal.speed = config.sim_speed_max;
+            animal.process_metabolism(&config, config.sim_rotation_accel);
+
+            approx::assert_relative_eq!(animal.energy(), 1.0 - 0.0001 - 0.0003 - 0.0002);
+            assert_eq!(animal.lifetime, 1);
+        }
+
+        #[test]
+        fn stops_animals_that_run_out_of_energy() {
+            let config = Config::default();
+            let mut animal = animal(&config);
+            let position = animal.position();
+
+            animal.energy = 0.0001;
+            animal.process_metabolism(&config, 0.0);
+
+            assert!(!animal.is_alive());
+            approx::assert_relative_eq!(animal.speed, 0.0);
+
+            animal.process_movement();
+            assert_eq!(animal.position(), position);
         }
     }
 }
@@ -2,19 +2,21 @@ use crate::*;
 
 pub struct AnimalIndividual {
     crate fitness: f32,
+    crate objectives: Vec<f32>,
     crate chromosome: ga::Chromosome,
 }
 
 impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
+    pub fn from_animal(animal: &Animal, fitness: f32) -> Self {
         Self {
-            fitness: animal.satiation as f32,
+            fitness,
+            objectives: Vec::new(),
             chromosome: animal.as_chromosome(),
         }
     }
 
-    pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(config, rng, self.chromosome)
+    pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore, species: Species) -> Animal {
+        Animal::from_chromosome(config, rng, species, self.chromosome)
     }
 }
 
@@ -22,6 +24,7 @@ impl ga::Individual for AnimalIndividual {
     fn create(chromosome: ga::Chromosome) -> Self {
         Self {
             fitness: 0.0,
+            objectives: Vec::new(),
             chromosome,
         }
     }
@@ -34,3 +37,9 @@ impl ga::Individual for AnimalIndividual {
         self.fitness
     }
 }
+
+impl ga::MultiObjectiveIndividual for AnimalIndividual {
+    fn objectives(&self) -> &[f32] {
+        &self.objectives
+    }
+}
@@ -0,0 +1,101 @@
+use crate::*;
+
+impl BehaviorConfig {
+    /// Summarizes what given animal has been doing, so that it can be
+    /// compared with others (e.g. through `NoveltyArchive`).
+    pub fn describe(&self, animal: &Animal) -> Vec<f32> {
+        match *self {
+            Self::Endpoints => {
+                let start = animal.trail().first().copied().unwrap_or(animal.position());
+                let end = animal.position();
+
+                vec![start.x, start.y, end.x, end.y]
+            }
+
+            Self::Cells { resolution } => {
+                let mut cells = vec![0.0; resolution * resolution];
+
+                let cell = |coord: f32| ((coord * resolution as f32) as usize).min(resolution - 1);
+
+                for position in animal.trail().iter().chain(Some(&animal.position())) {
+                    cells[cell(position.y) * resolution + cell(position.x)] = 1.0;
+                }
+
+                cells
+            }
+        }
+    }
+}
+
+impl AnimalMeasure {
+    pub fn measure(self, animal: &Animal) -> f32 {
+        let lifetime = animal.lifetime().max(1) as f32;
+
+        match self {
+            Self::AverageSpeed => animal.average_speed(),
+            Self::TurningRate => animal.turning() / lifetime,
+            Self::Distance => animal.distance(),
+            Self::Nutrition => animal.nutrition(),
+            Self::Energy => animal.energy(),
+            Self::Lifetime => animal.lifetime() as f32,
+        }
+    }
+}
+
+impl DimensionConfig {
+    /// Measures given animal, mapping the result into `0.0..=1.0`.
+    pub fn normalize(&self, animal: &Animal) -> f32 {
+        let value = self.measure.measure(animal);
+
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animal() -> Animal {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng, Species::Prey);
+
+        animal.trail = vec![na::Point2::new(0.1, 0.1), na::Point2::new(0.9, 0.2)];
+        animal.position = na::Point2::new(0.9, 0.95);
+        animal
+    }
+
+    #[test]
+    fn endpoints() {
+        let actual = BehaviorConfig::Endpoints.describe(&animal());
+
+        approx::assert_relative_eq!(actual.as_slice(), [0.1, 0.1, 0.9, 0.95].as_ref());
+    }
+
+    #[test]
+    fn cells() {
+        let actual = BehaviorConfig::Cells { resolution: 2 }.describe(&animal());
+
+        approx::assert_relative_eq!(actual.as_slice(), [1.0, 1.0, 0.0, 1.0].as_ref());
+    }
+
+    #[test]
+    fn normalized_dimension() {
+        let mut animal = animal();
+
+        animal.lifetime = 100;
+        animal.turning = 30.0;
+
+        let dimension = |min, max| DimensionConfig {
+            measure: AnimalMeasure::TurningRate,
+            min,
+            max,
+            cells: 10,
+        };
+
+        approx::assert_relative_eq!(dimension(0.1, 0.5).normalize(&animal), 0.5);
+        approx::assert_relative_eq!(dimension(0.5, 1.0).normalize(&animal), 0.0);
+        approx::assert_relative_eq!(dimension(0.0, 0.2).normalize(&animal), 1.0);
+    }
+}
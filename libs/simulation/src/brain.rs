@@ -8,14 +8,18 @@ pub struct Brain {
 }
 
 impl Brain {
-    crate fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
-        let nn = nn::Network::random(rng, &Self::topology(config));
+    crate fn random(config: &Config, rng: &mut dyn RngCore, species: Species) -> Self {
+        let nn = nn::Network::random(rng, &Self::topology(config, species));
 
         Self::new(config, nn)
     }
 
-    crate fn from_chromosome(config: &Config, chromosome: ga::Chromosome) -> Self {
-        let nn = nn::Network::from_weights(&Self::topology(config), chromosome);
+    crate fn from_chromosome(
+        config: &Config,
+        species: Species,
+        chromosome: ga::Chromosome,
+    ) -> Self {
+        let nn = nn::Network::from_weights(&Self::topology(config, species), chromosome);
 
         Self::new(config, nn)
     }
@@ -45,10 +49,27 @@ impl Brain {
         }
     }
 
-    fn topology(config: &Config) -> [nn::LayerTopology; 3] {
+    /// Number of neurons in each layer; brains (e.g. of migrating animals)
+    /// can only be moved between simulations where these match.
+    crate fn layers(config: &Config, species: Species) -> Vec<usize> {
+        Self::topology(config, species)
+            .iter()
+            .map(|layer| layer.neurons)
+            .collect()
+    }
+
+    /// Number of genes (i.e. weights and biases) of a brain.
+    crate fn genes(config: &Config, species: Species) -> usize {
+        Self::layers(config, species)
+            .windows(2)
+            .map(|layers| (layers[0] + 1) * layers[1])
+            .sum()
+    }
+
+    fn topology(config: &Config, species: Species) -> [nn::LayerTopology; 3] {
         [
             nn::LayerTopology {
-                neurons: config.eye_cells,
+                neurons: config.eye_cells * species.eye_channels(config),
             },
             nn::LayerTopology {
                 neurons: config.brain_neurons,
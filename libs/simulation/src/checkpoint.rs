@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Snapshot of a simulation's population, which can be used to resume the
+/// training later.
+///
+/// Only the brains get saved - positions of animals, food etc. are
+/// randomized again when the checkpoint gets restored. Neither is the state
+/// some kinds of evolution keep between generations (e.g. CMA-ES'
+/// distribution), so simulations using them can't be restored.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Checkpoint {
+    pub generation: usize,
+    pub config: Config,
+    pub animals: Vec<AnimalCheckpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnimalCheckpoint {
+    pub species: Species,
+    pub genes: Vec<f32>,
+}
+
+impl From<&Animal> for AnimalCheckpoint {
+    fn from(animal: &Animal) -> Self {
+        Self {
+            species: animal.species,
+            genes: animal.as_chromosome().into_iter().collect(),
+        }
+    }
+}
@@ -1,7 +1,19 @@
+pub use self::{evolution_config::*, fitness_config::*, food_spawner_config::*, ga_config::*};
+
+mod evolution_config;
+mod fitness_config;
+mod food_spawner_config;
+mod ga_config;
+mod presets;
+
 use crate::*;
+use serde::de::{self, Deserializer, Unexpected};
 
+/// Simulation's parameters; when deserializing, the missing ones are taken
+/// from `Config::default()`, so that configs written for older versions keep
+/// working.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub brain_neurons: usize,
 
@@ -9,20 +21,59 @@ pub struct Config {
     pub eye_fov_angle: f32,
     pub eye_cells: usize,
 
+    pub energy_initial: f32,
+    pub energy_max: f32,
+    pub energy_food: f32,
+    pub energy_cost_idle: f32,
+    pub energy_cost_speed: f32,
+    pub energy_cost_rotation: f32,
+    pub energy_fitness_survival: f32,
+    pub energy_fitness_energy: f32,
+
     pub food_size: f32,
+    pub food_kinds: Vec<FoodKind>,
+    #[serde(deserialize_with = "FoodSpawnerConfig::deserialize_legacy")]
+    pub food_spawner: FoodSpawnerConfig,
+    pub food_clusters: usize,
+    pub food_cluster_radius: f32,
+    pub food_regrowth: usize,
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub food_finite: bool,
+    pub food_speed: f32,
+    pub food_season_length: usize,
+    pub food_season_amplitude: f32,
+
+    pub fitness: FitnessConfig,
+    pub evolution: EvolutionConfig,
+    pub ga: GaConfig,
+
+    /// Legacy alias for `ga.reverse` (0 or 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ga_reverse: Option<usize>,
 
-    pub ga_reverse: usize,
-    pub ga_mut_chance: f32,
-    pub ga_mut_coeff: f32,
+    /// Legacy alias for `ga.mutation.chance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ga_mut_chance: Option<f32>,
+
+    /// Legacy alias for `ga.mutation.coeff`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ga_mut_coeff: Option<f32>,
 
     pub sim_speed_min: f32,
     pub sim_speed_max: f32,
     pub sim_speed_accel: f32,
     pub sim_rotation_accel: f32,
     pub sim_generation_length: usize,
+    #[serde(deserialize_with = "deserialize_flag")]
+    pub sim_continuous: bool,
+    pub sim_max_age: usize,
+    pub sim_birth_satiation: usize,
+    pub sim_min_animals: usize,
+    pub sim_max_animals: usize,
 
     pub world_animals: usize,
     pub world_foods: usize,
+    pub world_predators: usize,
 }
 
 impl Default for Config {
@@ -34,20 +85,856 @@ impl Default for Config {
             eye_fov_angle: PI + FRAC_PI_4,
             eye_cells: 9,
             //
+            energy_initial: 1.0,
+            energy_max: 2.0,
+            energy_food: 0.5,
+            energy_cost_idle: 0.0001,
+            energy_cost_speed: 0.0003,
+            energy_cost_rotation: 0.0002,
+            energy_fitness_survival: 1.0,
+            energy_fitness_energy: 1.0,
+            //
             food_size: 0.01,
+            food_kinds: vec![FoodKind::default()],
+            food_spawner: FoodSpawnerConfig::Uniform,
+            food_clusters: 3,
+            food_cluster_radius: 0.1,
+            food_regrowth: 0,
+            food_finite: false,
+            food_speed: 0.0,
+            food_season_length: 0,
+            food_season_amplitude: 0.5,
             //
-            ga_reverse: 0,
-            ga_mut_chance: 0.01,
-            ga_mut_coeff: 0.3,
+            fitness: Default::default(),
+            evolution: Default::default(),
+            ga: Default::default(),
+            ga_reverse: None,
+            ga_mut_chance: None,
+            ga_mut_coeff: None,
             //
             sim_speed_min: 0.001,
             sim_speed_max: 0.005,
             sim_speed_accel: 0.2,
             sim_rotation_accel: FRAC_PI_2,
             sim_generation_length: 2500,
+            sim_continuous: false,
+            sim_max_age: 5000,
+            sim_birth_satiation: 5,
+            sim_min_animals: 10,
+            sim_max_animals: 80,
             //
             world_animals: 40,
             world_foods: 60,
+            world_predators: 0,
+        }
+    }
+}
+
+impl Config {
+    /// Checks whether all the parameters are within their allowed ranges.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut err = ConfigError { fields: Vec::new() };
+
+        err.check(
+            "brain_neurons",
+            self.brain_neurons,
+            self.brain_neurons >= 1,
+            ">= 1",
+        );
+
+        err.check(
+            "eye_fov_range",
+            self.eye_fov_range,
+            positive(self.eye_fov_range),
+            "> 0",
+        );
+        err.check(
+            "eye_fov_angle",
+            self.eye_fov_angle,
+            positive(self.eye_fov_angle),
+            "> 0",
+        );
+        err.check("eye_cells", self.eye_cells, self.eye_cells >= 1, ">= 1");
+
+        err.check(
+            "energy_max",
+            self.energy_max,
+            positive(self.energy_max),
+            "> 0",
+        );
+
+        err.check(
+            "energy_initial",
+            self.energy_initial,
+            positive(self.energy_initial) && self.energy_initial <= self.energy_max,
+            "> 0 and <= energy_max",
+        );
+
+        for (field, value) in &[
+            ("energy_food", self.energy_food),
+            ("energy_cost_idle", self.energy_cost_idle),
+            ("energy_cost_speed", self.energy_cost_speed),
+            ("energy_cost_rotation", self.energy_cost_rotation),
+            ("energy_fitness_survival", self.energy_fitness_survival),
+            ("energy_fitness_energy", self.energy_fitness_energy),
+        ] {
+            err.check(*field, value, non_negative(*value), ">= 0");
+        }
+
+        err.check("food_size", self.food_size, positive(self.food_size), "> 0");
+
+        err.check(
+            "food_kinds",
+            self.food_kinds.len(),
+            !self.food_kinds.is_empty(),
+            "at least one kind",
+        );
+
+        for (id, kind) in self.food_kinds.iter().enumerate() {
+            let field = |name| format!("food_kinds[{}].{}", id, name);
+
+            err.check(field("size"), kind.size, positive(kind.size), "> 0");
+            err.check(
+                field("frequency"),
+                kind.frequency,
+                positive(kind.frequency),
+                "> 0",
+            );
+        }
+
+        err.check(
+            "food_clusters",
+            self.food_clusters,
+            self.food_clusters >= 1,
+            ">= 1",
+        );
+
+        err.check(
+            "food_cluster_radius",
+            self.food_cluster_radius,
+            non_negative(self.food_cluster_radius),
+            ">= 0",
+        );
+
+        err.check(
+            "food_speed",
+            self.food_speed,
+            non_negative(self.food_speed),
+            ">= 0",
+        );
+
+        err.check(
+            "food_season_amplitude",
+            self.food_season_amplitude,
+            fraction(self.food_season_amplitude),
+            "between 0 and 1",
+        );
+
+        validate_fitness(&mut err, "fitness", &self.fitness);
+
+        match &self.evolution {
+            EvolutionConfig::Generational => (),
+
+            EvolutionConfig::MapElites { dimensions } => {
+                err.check(
+                    "evolution.dimensions",
+                    dimensions.len(),
+                    !dimensions.is_empty(),
+                    "at least one dimension",
+                );
+
+                for (id, dimension) in dimensions.iter().enumerate() {
+                    let field = |name| format!("evolution.dimensions[{}].{}", id, name);
+
+                    err.check(
+                        field("cells"),
+                        dimension.cells,
+                        dimension.cells >= 1,
+                        ">= 1",
+                    );
+
+                    err.check(
+                        field("max"),
+                        dimension.max,
+                        dimension.max > dimension.min,
+                        "> min",
+                    );
+                }
+            }
+
+            EvolutionConfig::Nsga2 { objectives } => {
+                err.check(
+                    "evolution.objectives",
+                    objectives.len(),
+                    objectives.len() >= 2,
+                    "at least two objectives",
+                );
+
+                for (id, objective) in objectives.iter().enumerate() {
+                    validate_fitness(
+                        &mut err,
+                        &format!("evolution.objectives[{}]", id),
+                        objective,
+                    );
+                }
+            }
+
+            EvolutionConfig::MuLambda { mu, sigma }
+            | EvolutionConfig::MuPlusLambda { mu, sigma } => {
+                err.check("evolution.mu", mu, *mu >= 1, ">= 1");
+                err.check("evolution.sigma", sigma, positive(*sigma), "> 0");
+            }
+
+            EvolutionConfig::Cmaes { sigma } => {
+                err.check("evolution.sigma", sigma, positive(*sigma), "> 0");
+            }
+        }
+
+        // Continuous simulation never evolves a whole generation at once, so
+        // the other kinds of evolution would be silently ignored
+        err.check(
+            "evolution.type",
+            self.evolution.name(),
+            !self.sim_continuous || matches!(self.evolution, EvolutionConfig::Generational),
+            "generational, since sim_continuous is set",
+        );
+
+        if let SelectionConfig::Tournament { size } = self.ga.selection {
+            err.check("ga.selection.size", size, size >= 1, ">= 1");
+        }
+
+        if let Some(speciation) = &self.ga.speciation {
+            err.check(
+                "ga.speciation.threshold",
+                speciation.threshold,
+                positive(speciation.threshold),
+                "> 0",
+            );
+
+            // Continuous simulation breeds animals one pair at a time, so
+            // there are no species whose fitness could be shared
+            err.check(
+                "ga.speciation",
+                "enabled",
+                !self.sim_continuous,
+                "disabled, since sim_continuous is set",
+            );
+        }
+
+        err.check(
+            "ga.crossover_chance",
+            self.ga.crossover_chance,
+            fraction(self.ga.crossover_chance),
+            "between 0 and 1",
+        );
+
+        validate_mutation(&mut err, "ga.mutation", &self.ga.mutation);
+
+        for (id, extra) in self.ga.extra_mutations.iter().enumerate() {
+            let field = format!("ga.extra_mutations[{}]", id);
+
+            err.check(
+                format!("{}.chance", field),
+                extra.chance,
+                fraction(extra.chance),
+                "between 0 and 1",
+            );
+
+            validate_mutation(&mut err, &format!("{}.mutation", field), &extra.mutation);
+        }
+
+        if let Some(reverse) = self.ga_reverse {
+            err.check("ga_reverse", reverse, flag(reverse), "0 or 1");
+        }
+
+        if let Some(chance) = self.ga_mut_chance {
+            err.check("ga_mut_chance", chance, fraction(chance), "between 0 and 1");
+        }
+
+        if let Some(coeff) = self.ga_mut_coeff {
+            err.check("ga_mut_coeff", coeff, non_negative(coeff), ">= 0");
+        }
+
+        err.check(
+            "sim_speed_min",
+            self.sim_speed_min,
+            non_negative(self.sim_speed_min),
+            ">= 0",
+        );
+
+        err.check(
+            "sim_speed_max",
+            self.sim_speed_max,
+            positive(self.sim_speed_max) && self.sim_speed_max >= self.sim_speed_min,
+            "> 0 and >= sim_speed_min",
+        );
+
+        err.check(
+            "sim_speed_accel",
+            self.sim_speed_accel,
+            non_negative(self.sim_speed_accel),
+            ">= 0",
+        );
+
+        err.check(
+            "sim_rotation_accel",
+            self.sim_rotation_accel,
+            positive(self.sim_rotation_accel),
+            "> 0",
+        );
+
+        err.check(
+            "sim_generation_length",
+            self.sim_generation_length,
+            self.sim_generation_length >= 1,
+            ">= 1",
+        );
+
+        err.check(
+            "sim_max_age",
+            self.sim_max_age,
+            self.sim_max_age >= 1,
+            ">= 1",
+        );
+        err.check(
+            "sim_birth_satiation",
+            self.sim_birth_satiation,
+            self.sim_birth_satiation >= 1,
+            ">= 1",
+        );
+        err.check(
+            "sim_min_animals",
+            self.sim_min_animals,
+            self.sim_min_animals >= 1,
+            ">= 1",
+        );
+
+        err.check(
+            "sim_max_animals",
+            self.sim_max_animals,
+            self.sim_max_animals >= self.sim_min_animals,
+            ">= sim_min_animals",
+        );
+
+        err.check(
+            "world_animals",
+            self.world_animals,
+            self.world_animals >= 1,
+            ">= 1",
+        );
+
+        if err.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Moves values of the legacy, flat fields (e.g. `ga_reverse`) into
+    /// their new places, so that old configs keep working.
+    crate fn upgrade(mut self) -> Self {
+        if let Some(reverse) = self.ga_reverse.take() {
+            self.ga.reverse = reverse == 1;
+        }
+
+        if self.ga_mut_chance.is_some() || self.ga_mut_coeff.is_some() {
+            let (chance, coeff) = match self.ga.mutation {
+                MutationConfig::Gaussian { chance, coeff } => (chance, coeff),
+            };
+
+            self.ga.mutation = MutationConfig::Gaussian {
+                chance: self.ga_mut_chance.take().unwrap_or(chance),
+                coeff: self.ga_mut_coeff.take().unwrap_or(coeff),
+            };
+        }
+
+        self
+    }
+}
+
+/// Deserializes a flag either as a boolean or as the number older configs used
+/// for it (0 or 1).
+fn deserialize_flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Bool(bool),
+        Legacy(u64),
+    }
+
+    match Value::deserialize(deserializer)? {
+        Value::Bool(value) => Ok(value),
+        Value::Legacy(0) => Ok(false),
+        Value::Legacy(1) => Ok(true),
+        Value::Legacy(value) => Err(de::Error::invalid_value(
+            Unexpected::Unsigned(value),
+            &"true, false, 0 or 1",
+        )),
+    }
+}
+
+fn validate_mutation(err: &mut ConfigError, field: &str, mutation: &MutationConfig) {
+    match *mutation {
+        MutationConfig::Gaussian { chance, coeff } => {
+            err.check(
+                format!("{}.chance", field),
+                chance,
+                fraction(chance),
+                "between 0 and 1",
+            );
+            err.check(
+                format!("{}.coeff", field),
+                coeff,
+                non_negative(coeff),
+                ">= 0",
+            );
+        }
+    }
+}
+
+fn validate_fitness(err: &mut ConfigError, field: &str, fitness: &FitnessConfig) {
+    match fitness {
+        FitnessConfig::Novelty(novelty) => {
+            err.check(format!("{}.k", field), novelty.k, novelty.k >= 1, ">= 1");
+
+            if let BehaviorConfig::Cells { resolution } = novelty.behavior {
+                err.check(
+                    format!("{}.behavior.resolution", field),
+                    resolution,
+                    resolution >= 1,
+                    ">= 1",
+                );
+            }
+
+            err.check(
+                format!("{}.archive_additions", field),
+                novelty.archive_additions,
+                novelty.archive_additions <= novelty.archive_size,
+                "<= archive_size",
+            );
+        }
+
+        FitnessConfig::Weighted { terms } => {
+            for (id, term) in terms.iter().enumerate() {
+                let field = format!("{}.terms[{}]", field, id);
+
+                err.check(
+                    format!("{}.weight", field),
+                    term.weight,
+                    term.weight.is_finite() && non_negative(term.weight),
+                    "a finite number >= 0",
+                );
+
+                validate_fitness(err, &format!("{}.fitness", field), &term.fitness);
+            }
+        }
+
+        _ => (),
+    }
+}
+
+fn positive(value: f32) -> bool {
+    value > 0.0
+}
+
+fn non_negative(value: f32) -> bool {
+    value >= 0.0
+}
+
+fn fraction(value: f32) -> bool {
+    (0.0..=1.0).contains(&value)
+}
+
+fn flag(value: usize) -> bool {
+    value <= 1
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FoodKind {
+    /// How much energy (as a multiple of `energy_food`) this food gives;
+    /// negative for poison
+    pub nutrition: f32,
+
+    /// Size of this food, as a multiple of `food_size`
+    pub size: f32,
+
+    /// How often this kind of food grows, relatively to other kinds
+    pub frequency: f32,
+}
+
+impl Default for FoodKind {
+    fn default() -> Self {
+        Self {
+            nutrition: 1.0,
+            size: 1.0,
+            frequency: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn accepts_default_config() {
+            assert_eq!(Config::default().validate(), Ok(()));
+        }
+
+        #[test]
+        fn reports_all_invalid_fields() {
+            let config = Config {
+                eye_cells: 0,
+                food_kinds: vec![FoodKind {
+                    size: -1.0,
+                    ..Default::default()
+                }],
+                ga_mut_chance: Some(1.5),
+                sim_speed_max: f32::NAN,
+                ..Default::default()
+            };
+
+            let fields: Vec<_> = config
+                .validate()
+                .unwrap_err()
+                .fields
+                .into_iter()
+                .map(|field| field.field)
+                .collect();
+
+            assert_eq!(
+                fields,
+                [
+                    "eye_cells",
+                    "food_kinds[0].size",
+                    "ga_mut_chance",
+                    "sim_speed_max"
+                ]
+            );
+        }
+
+        #[test]
+        fn reports_invalid_map_elites_dimensions() {
+            let config = Config {
+                evolution: EvolutionConfig::MapElites {
+                    dimensions: vec![DimensionConfig {
+                        measure: AnimalMeasure::Distance,
+                        min: 1.0,
+                        max: 1.0,
+                        cells: 0,
+                    }],
+                },
+                ..Default::default()
+            };
+
+            let fields: Vec<_> = config
+                .validate()
+                .unwrap_err()
+                .fields
+                .into_iter()
+                .map(|field| field.field)
+                .collect();
+
+            assert_eq!(
+                fields,
+                [
+                    "evolution.dimensions[0].cells",
+                    "evolution.dimensions[0].max"
+                ]
+            );
+        }
+
+        #[test]
+        fn reports_invalid_ga_operators() {
+            let config = Config {
+                ga: GaConfig {
+                    crossover_chance: 2.0,
+                    extra_mutations: vec![ExtraMutationConfig {
+                        chance: 0.5,
+                        mutation: MutationConfig::Gaussian {
+                            chance: 0.5,
+                            coeff: -1.0,
+                        },
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let fields: Vec<_> = config
+                .validate()
+                .unwrap_err()
+                .fields
+                .into_iter()
+                .map(|field| field.field)
+                .collect();
+
+            assert_eq!(
+                fields,
+                [
+                    "ga.crossover_chance",
+                    "ga.extra_mutations[0].mutation.coeff"
+                ]
+            );
+        }
+
+        #[test]
+        fn rejects_evolution_backends_for_continuous_simulation() {
+            let config = Config {
+                evolution: EvolutionConfig::MapElites {
+                    dimensions: vec![DimensionConfig {
+                        measure: AnimalMeasure::Distance,
+                        min: 0.0,
+                        max: 1.0,
+                        cells: 4,
+                    }],
+                },
+                sim_continuous: true,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                config.validate().unwrap_err().to_string(),
+                "invalid config:\n- evolution.type = map_elites (expected generational, since sim_continuous is set)"
+            );
+        }
+
+        #[test]
+        fn rejects_speciation_for_continuous_simulation() {
+            let config = Config {
+                ga: GaConfig {
+                    speciation: Some(SpeciationConfig { threshold: 0.1 }),
+                    ..Default::default()
+                },
+                sim_continuous: true,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                config.validate().unwrap_err().to_string(),
+                "invalid config:\n- ga.speciation = enabled (expected disabled, since sim_continuous is set)"
+            );
+        }
+
+        #[test]
+        fn rejects_negative_fitness_weights() {
+            let config = Config {
+                fitness: FitnessConfig::Weighted {
+                    terms: vec![
+                        WeightedFitnessConfig {
+                            weight: 1.0,
+                            fitness: FitnessConfig::Nutrition,
+                        },
+                        WeightedFitnessConfig {
+                            weight: -1.0,
+                            fitness: FitnessConfig::Distance,
+                        },
+                    ],
+                },
+                ..Default::default()
+            };
+
+            assert_eq!(
+                config.validate().unwrap_err().to_string(),
+                "invalid config:\n- fitness.terms[1].weight = -1 (expected a finite number >= 0)"
+            );
+        }
+
+        #[test]
+        fn requires_animals_to_eat_before_giving_birth() {
+            let config = Config {
+                sim_birth_satiation: 0,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                config.validate().unwrap_err().to_string(),
+                "invalid config:\n- sim_birth_satiation = 0 (expected >= 1)"
+            );
+        }
+
+        #[test]
+        fn describes_allowed_ranges() {
+            let config = Config {
+                world_animals: 0,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                config.validate().unwrap_err().to_string(),
+                "invalid config:\n- world_animals = 0 (expected >= 1)"
+            );
+        }
+    }
+
+    mod upgrade {
+        use super::*;
+
+        #[test]
+        fn moves_legacy_fields_into_ga() {
+            let config = Config {
+                ga_reverse: Some(1),
+                ga_mut_coeff: Some(0.5),
+                ..Default::default()
+            };
+
+            let config = config.upgrade();
+
+            assert!(config.ga.reverse);
+            assert!(config.ga_reverse.is_none());
+            assert!(config.ga_mut_coeff.is_none());
+
+            match config.ga.mutation {
+                MutationConfig::Gaussian { chance, coeff } => {
+                    approx::assert_relative_eq!(chance, 0.01);
+                    approx::assert_relative_eq!(coeff, 0.5);
+                }
+            }
+        }
+    }
+
+    mod deserialize {
+        use super::*;
+
+        fn config(overrides: serde_json::Value) -> Config {
+            let mut config = serde_json::to_value(Config::default()).unwrap();
+
+            for (key, value) in overrides.as_object().unwrap() {
+                config[key] = value.clone();
+            }
+
+            serde_json::from_value(config).unwrap()
+        }
+
+        #[test]
+        fn accepts_typed_sections() {
+            let config = config(serde_json::json!({
+                "ga": {
+                    "reverse": true,
+                    "selection": { "type": "tournament", "size": 3 },
+                    "crossover": { "type": "single_point" },
+                    "mutation": { "type": "gaussian", "chance": 0.1, "coeff": 0.2 },
+                },
+                "fitness": { "mode": "nutrition" },
+            }));
+
+            assert!(config.ga.reverse);
+            assert!(matches!(
+                config.ga.selection,
+                SelectionConfig::Tournament { size: 3 }
+            ));
+            assert!(matches!(config.ga.crossover, CrossoverConfig::SinglePoint));
+            approx::assert_relative_eq!(config.ga.crossover_chance, 1.0);
+            assert!(matches!(config.fitness, FitnessConfig::Nutrition));
+        }
+
+        #[test]
+        fn accepts_nsga2_objectives() {
+            let config = config(serde_json::json!({
+                "evolution": {
+                    "type": "nsga2",
+                    "objectives": [{ "mode": "nutrition" }, { "mode": "survival" }],
+                },
+            }));
+
+            match config.evolution {
+                EvolutionConfig::Nsga2 { objectives } => {
+                    assert!(matches!(objectives[0], FitnessConfig::Nutrition));
+                    assert!(matches!(objectives[1], FitnessConfig::Survival));
+                }
+
+                evolution => panic!("unexpected evolution: {:?}", evolution),
+            }
+        }
+
+        #[test]
+        fn accepts_baseline_flat_config() {
+            let config: Config = serde_json::from_str(
+                r#"{
+                    "brain_neurons": 9,
+                    "eye_fov_range": 0.25,
+                    "eye_fov_angle": 3.926991,
+                    "eye_cells": 9,
+                    "food_size": 0.01,
+                    "ga_reverse": 1,
+                    "ga_mut_chance": 0.02,
+                    "ga_mut_coeff": 0.3,
+                    "sim_speed_min": 0.001,
+                    "sim_speed_max": 0.005,
+                    "sim_speed_accel": 0.2,
+                    "sim_rotation_accel": 1.5707964,
+                    "sim_generation_length": 2500,
+                    "world_animals": 30,
+                    "world_foods": 60
+                }"#,
+            )
+            .unwrap();
+
+            assert_eq!(config.validate(), Ok(()));
+
+            let config = config.upgrade();
+
+            assert_eq!(config.world_animals, 30);
+            assert!(config.ga.reverse);
+            assert!(!config.sim_continuous);
+
+            match config.ga.mutation {
+                MutationConfig::Gaussian { chance, coeff } => {
+                    approx::assert_relative_eq!(chance, 0.02);
+                    approx::assert_relative_eq!(coeff, 0.3);
+                }
+            }
+        }
+
+        #[test]
+        fn accepts_legacy_flat_fields() {
+            let config = config(serde_json::json!({
+                "ga_reverse": 1,
+                "ga_mut_chance": 0.2,
+                "ga_mut_coeff": 0.4,
+            }));
+
+            assert_eq!(config.ga_reverse, Some(1));
+            assert_eq!(config.ga_mut_chance, Some(0.2));
+            assert_eq!(config.ga_mut_coeff, Some(0.4));
+        }
+
+        #[test]
+        fn accepts_typed_and_legacy_flags() {
+            let typed = config(serde_json::json!({
+                "food_spawner": "clustered",
+                "food_finite": true,
+                "sim_continuous": false,
+            }));
+
+            assert_eq!(typed.food_spawner, FoodSpawnerConfig::Clustered);
+            assert!(typed.food_finite);
+            assert!(!typed.sim_continuous);
+
+            let legacy = config(serde_json::json!({
+                "food_spawner": 1,
+                "food_finite": 0,
+                "sim_continuous": 1,
+            }));
+
+            assert_eq!(legacy.food_spawner, FoodSpawnerConfig::Clustered);
+            assert!(!legacy.food_finite);
+            assert!(legacy.sim_continuous);
+        }
+
+        #[test]
+        fn rejects_invalid_legacy_flags() {
+            let mut config = serde_json::to_value(Config::default()).unwrap();
+            config["food_finite"] = serde_json::json!(2);
+
+            assert!(serde_json::from_value::<Config>(config).is_err());
         }
     }
 }
@@ -0,0 +1,105 @@
+use crate::*;
+
+/// How each new generation is created.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EvolutionConfig {
+    /// The whole population is replaced with children of its fittest members
+    Generational,
+
+    /// Population is bred from an archive of elites, where each elite is the
+    /// fittest animal found so far for some kind of behavior (described by
+    /// `dimensions`); only for the non-continuous simulation
+    MapElites { dimensions: Vec<DimensionConfig> },
+
+    /// Population is judged on many objectives at once (instead of the single
+    /// `Config::fitness`), preferring animals no other animal beats on all of
+    /// them - e.g. `[{ mode = "nutrition" }, { mode = "survival" }]`
+    Nsga2 { objectives: Vec<FitnessConfig> },
+
+    /// (μ, λ) evolution strategy: children are the `mu` fittest animals with
+    /// Gaussian noise (of `sigma`) added to their genes
+    MuLambda { mu: usize, sigma: f32 },
+
+    /// (μ + λ) evolution strategy: like `MuLambda`, but parents compete with
+    /// their children
+    MuPlusLambda { mu: usize, sigma: f32 },
+
+    /// Separable CMA-ES, starting with step size `sigma`
+    Cmaes { sigma: f32 },
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self::Generational
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DimensionConfig {
+    pub measure: AnimalMeasure,
+
+    /// Values below `min` and above `max` fall into the first and the last
+    /// cell respectively
+    pub min: f32,
+    pub max: f32,
+
+    /// Number of cells this dimension is split into
+    pub cells: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimalMeasure {
+    /// Distance travelled per step
+    AverageSpeed,
+
+    /// Rotation (in radians) per step
+    TurningRate,
+
+    Distance,
+    Nutrition,
+    Energy,
+    Lifetime,
+}
+
+impl EvolutionConfig {
+    /// Name of this kind of evolution, as in the `type` tag.
+    crate fn name(&self) -> &'static str {
+        match self {
+            Self::Generational => "generational",
+            Self::MapElites { .. } => "map_elites",
+            Self::Nsga2 { .. } => "nsga2",
+            Self::MuLambda { .. } => "mu_lambda",
+            Self::MuPlusLambda { .. } => "mu_plus_lambda",
+            Self::Cmaes { .. } => "cmaes",
+        }
+    }
+
+    /// Whether this kind of evolution keeps any state between generations
+    /// (an archive, parents or a distribution), which checkpoints don't save.
+    crate fn keeps_state(&self) -> bool {
+        !matches!(self, Self::Generational | Self::MuLambda { .. })
+    }
+
+    /// Creates the optimizer for NSGA-II and evolution strategies; returns
+    /// `None` for the other kinds of evolution.
+    crate fn optimizer(
+        &self,
+        ga: &GaConfig,
+    ) -> Option<Box<dyn ga::Optimizer<AnimalIndividual> + Send>> {
+        match *self {
+            Self::Nsga2 { .. } => Some(Box::new(ga::Nsga2::new(
+                ga.crossover.clone(),
+                ga.mutation.clone(),
+            ))),
+            Self::MuLambda { mu, sigma } => Some(Box::new(ga::EvolutionStrategy::comma(mu, sigma))),
+            Self::MuPlusLambda { mu, sigma } => {
+                Some(Box::new(ga::EvolutionStrategy::plus(mu, sigma)))
+            }
+            Self::Cmaes { sigma } => Some(Box::new(ga::Cmaes::new(sigma))),
+            _ => None,
+        }
+    }
+}
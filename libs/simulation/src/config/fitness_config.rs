@@ -0,0 +1,88 @@
+use crate::*;
+
+/// Fitness function used to evolve animals - see `FitnessFunction`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum FitnessConfig {
+    /// Only the food counts
+    Nutrition,
+
+    /// Food, plus bonuses for surviving and for the energy left (weighted by
+    /// `energy_fitness_survival` and `energy_fitness_energy`)
+    Energy,
+
+    /// Distance travelled
+    Distance,
+
+    /// Food eaten per unit of energy burned
+    EnergyEfficiency,
+
+    /// Time survived
+    Survival,
+
+    /// Novelty search - how different an animal behaves from the others
+    Novelty(NoveltyConfig),
+
+    /// Weighted sum of other fitness functions
+    Weighted { terms: Vec<WeightedFitnessConfig> },
+}
+
+impl Default for FitnessConfig {
+    fn default() -> Self {
+        Self::Energy
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeightedFitnessConfig {
+    /// Non-negative, since selection can't handle negative fitness
+    pub weight: f32,
+    pub fitness: FitnessConfig,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoveltyConfig {
+    /// Number of nearest neighbours novelty is measured against
+    pub k: usize,
+
+    /// How behavior of each animal is summarized
+    pub behavior: BehaviorConfig,
+
+    /// Maximum number of behaviors remembered (oldest ones are forgotten
+    /// first)
+    pub archive_size: usize,
+
+    /// Number of the most novel behaviors added to the archive after each
+    /// generation
+    pub archive_additions: usize,
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        Self {
+            k: 15,
+            behavior: BehaviorConfig::Endpoints,
+            archive_size: 500,
+            archive_additions: 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BehaviorConfig {
+    /// Where an animal has started and where it has ended up
+    Endpoints,
+
+    /// Which cells (of a `resolution` x `resolution` grid laid over the world)
+    /// an animal has visited
+    Cells { resolution: usize },
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self::Endpoints
+    }
+}
@@ -0,0 +1,40 @@
+use crate::*;
+use serde::de::{self, Deserializer, Unexpected};
+
+/// Where new food grows - see `FoodSpawner`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodSpawnerConfig {
+    /// Evenly across the entire world
+    Uniform,
+
+    /// In `food_clusters` circular patches (of `food_cluster_radius`), which
+    /// move to new places every generation
+    Clustered,
+}
+
+impl FoodSpawnerConfig {
+    /// Deserializes the spawner either by its name or by the number older
+    /// configs used for it (0 - uniform, 1 - clustered).
+    crate fn deserialize_legacy<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Named(FoodSpawnerConfig),
+            Legacy(u64),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Named(spawner) => Ok(spawner),
+            Value::Legacy(0) => Ok(Self::Uniform),
+            Value::Legacy(1) => Ok(Self::Clustered),
+            Value::Legacy(value) => Err(de::Error::invalid_value(
+                Unexpected::Unsigned(value),
+                &"uniform, clustered, 0 or 1",
+            )),
+        }
+    }
+}
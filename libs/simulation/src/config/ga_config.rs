@@ -0,0 +1,119 @@
+use crate::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GaConfig {
+    /// Whether fitness should be reversed, making animals *avoid* food
+    pub reverse: bool,
+    pub selection: SelectionConfig,
+    pub crossover: CrossoverConfig,
+
+    /// Chance of crossing parents over; otherwise the child starts as a copy
+    /// of one of them
+    #[serde(default = "default_crossover_chance")]
+    pub crossover_chance: f32,
+
+    pub mutation: MutationConfig,
+
+    /// Mutations applied after `mutation`, each with its own chance - e.g.
+    /// rare but large jumps on top of frequent, small nudges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_mutations: Vec<ExtraMutationConfig>,
+
+    /// When set, population is split into species of similar animals that
+    /// evolve side by side
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speciation: Option<SpeciationConfig>,
+}
+
+impl Default for GaConfig {
+    fn default() -> Self {
+        Self {
+            reverse: false,
+            selection: SelectionConfig::RouletteWheel,
+            crossover: CrossoverConfig::Uniform,
+            crossover_chance: default_crossover_chance(),
+            mutation: MutationConfig::Gaussian {
+                chance: 0.01,
+                coeff: 0.3,
+            },
+            extra_mutations: Vec::new(),
+            speciation: None,
+        }
+    }
+}
+
+fn default_crossover_chance() -> f32 {
+    1.0
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExtraMutationConfig {
+    /// Chance of applying this mutation to a child at all
+    pub chance: f32,
+    pub mutation: MutationConfig,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpeciationConfig {
+    /// Maximum (root-mean-square) distance between genes of animals from the
+    /// same species
+    pub threshold: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SelectionConfig {
+    RouletteWheel,
+    Tournament { size: usize },
+}
+
+impl ga::SelectionMethod for SelectionConfig {
+    fn select(&self, rng: &mut dyn RngCore, fitnesses: &[f32]) -> usize {
+        match *self {
+            Self::RouletteWheel => ga::RouletteWheelSelection::default().select(rng, fitnesses),
+            Self::Tournament { size } => ga::TournamentSelection::new(size).select(rng, fitnesses),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CrossoverConfig {
+    Uniform,
+    SinglePoint,
+}
+
+impl ga::CrossoverMethod for CrossoverConfig {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &ga::Chromosome,
+        parent_b: &ga::Chromosome,
+    ) -> ga::Chromosome {
+        match self {
+            Self::Uniform => ga::UniformCrossover::default().crossover(rng, parent_a, parent_b),
+            Self::SinglePoint => {
+                ga::SinglePointCrossover::default().crossover(rng, parent_a, parent_b)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MutationConfig {
+    Gaussian { chance: f32, coeff: f32 },
+}
+
+impl ga::MutationMethod for MutationConfig {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut ga::Chromosome) {
+        match *self {
+            Self::Gaussian { chance, coeff } => {
+                ga::GaussianMutation::new(chance, coeff).mutate(rng, child)
+            }
+        }
+    }
+}
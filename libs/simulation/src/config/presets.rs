@@ -0,0 +1,179 @@
+use crate::*;
+use serde_json::Value;
+
+impl Config {
+    /// Names of all the presets, as accepted by `Config::preset()`.
+    pub const PRESETS: &'static [&'static str] =
+        &["default", "large-world", "fast-evolve", "predator-prey"];
+
+    pub fn preset(name: &str) -> Option<Self> {
+        let default = Self::default();
+
+        let preset = match name {
+            "default" => default,
+
+            "large-world" => Self {
+                world_animals: 120,
+                world_foods: 180,
+                food_size: 0.007,
+                eye_fov_range: 0.2,
+                sim_generation_length: 4000,
+                ..default
+            },
+
+            "fast-evolve" => Self {
+                sim_generation_length: 1000,
+                ga: GaConfig {
+                    selection: SelectionConfig::Tournament { size: 3 },
+                    mutation: MutationConfig::Gaussian {
+                        chance: 0.05,
+                        coeff: 0.5,
+                    },
+                    ..default.ga
+                },
+                ..default
+            },
+
+            "predator-prey" => Self {
+                world_foods: 80,
+                world_predators: 8,
+                ..default
+            },
+
+            _ => return None,
+        };
+
+        Some(preset)
+    }
+
+    /// Merges given (possibly partial) config over this one - e.g.
+    /// `{ "world_animals": 10, "ga": { "reverse": true } }` changes just those
+    /// two parameters, leaving the rest as-is.
+    pub fn with_overrides(self, overrides: Value) -> Result<Self, serde_json::Error> {
+        let mut config = serde_json::to_value(self)?;
+
+        merge(&mut config, overrides);
+
+        serde_json::from_value(config)
+    }
+}
+
+/// Keys of the tags of config's enums (e.g. `ga.selection.type`).
+const TAGS: &[&str] = &["type", "mode"];
+
+fn merge(target: &mut Value, overrides: Value) {
+    match (target, overrides) {
+        // Switching to another variant of an enum - fields of the previous
+        // one would be rejected by the new one, so there's nothing to merge
+        (target, overrides) if changes_tag(target, &overrides) => {
+            *target = overrides;
+        }
+
+        (Value::Object(target), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match target.get_mut(&key) {
+                    Some(target) => merge(target, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        (target, overrides) => {
+            *target = overrides;
+        }
+    }
+}
+
+fn changes_tag(target: &Value, overrides: &Value) -> bool {
+    TAGS.iter().any(|tag| match overrides.get(tag) {
+        Some(tag_override) => target.get(tag) != Some(tag_override),
+        None => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    mod preset {
+        use super::*;
+
+        #[test]
+        fn all_presets_are_valid() {
+            for name in Config::PRESETS {
+                let config = Config::preset(name).unwrap();
+
+                assert_eq!(config.validate(), Ok(()), "preset: {}", name);
+            }
+        }
+
+        #[test]
+        fn rejects_unknown_presets() {
+            assert!(Config::preset("huge-world").is_none());
+        }
+    }
+
+    mod with_overrides {
+        use super::*;
+
+        #[test]
+        fn merges_partial_config_over_preset() {
+            let config = Config::preset("predator-prey")
+                .unwrap()
+                .with_overrides(json!({
+                    "world_animals": 10,
+                    "ga": { "reverse": true },
+                }))
+                .unwrap();
+
+            assert_eq!(config.world_animals, 10);
+            assert_eq!(config.world_predators, 8);
+            assert!(config.ga.reverse);
+            assert!(matches!(
+                config.ga.selection,
+                SelectionConfig::RouletteWheel
+            ));
+        }
+
+        #[test]
+        fn replaces_tagged_enums() {
+            let config = Config::default()
+                .with_overrides(json!({
+                    "ga": { "selection": { "type": "tournament", "size": 5 } },
+                }))
+                .unwrap();
+
+            assert!(matches!(
+                config.ga.selection,
+                SelectionConfig::Tournament { size: 5 }
+            ));
+        }
+
+        #[test]
+        fn switches_tagged_enum_variants() {
+            let config = Config::preset("fast-evolve")
+                .unwrap()
+                .with_overrides(json!({
+                    "ga": { "selection": { "type": "roulette_wheel" } },
+                }))
+                .unwrap();
+
+            assert!(matches!(
+                config.ga.selection,
+                SelectionConfig::RouletteWheel
+            ));
+        }
+
+        #[test]
+        fn rejects_unknown_fields() {
+            let err = Config::default()
+                .with_overrides(json!({ "world_animalz": 10 }))
+                .unwrap_err();
+
+            assert!(err.to_string().contains("world_animalz"));
+        }
+    }
+}
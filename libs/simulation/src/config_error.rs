@@ -0,0 +1,58 @@
+use std::{error, fmt};
+
+/// Returned by `Config::validate()`; lists all the invalid fields at once, so
+/// that they can be fixed in one go.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigError {
+    pub fields: Vec<InvalidField>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidField {
+    /// Name of the field, e.g. `eye_cells` or `food_kinds[1].size`
+    pub field: String,
+
+    /// Value the field has
+    pub value: String,
+
+    /// Human-readable range of values the field accepts, e.g. `>= 1`
+    pub expected: String,
+}
+
+impl ConfigError {
+    crate fn check(
+        &mut self,
+        field: impl Into<String>,
+        value: impl fmt::Display,
+        is_valid: bool,
+        expected: impl Into<String>,
+    ) {
+        if !is_valid {
+            self.fields.push(InvalidField {
+                field: field.into(),
+                value: value.to_string(),
+                expected: expected.into(),
+            });
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config:")?;
+
+        for field in &self.fields {
+            write!(
+                f,
+                "\n- {} = {} (expected {})",
+                field.field, field.value, field.expected
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for ConfigError {
+    //
+}
@@ -16,12 +16,12 @@ impl Eye {
         &self,
         position: na::Point2<f32>,
         rotation: na::Rotation2<f32>,
-        foods: &[Food],
+        targets: impl IntoIterator<Item = na::Point2<f32>>,
     ) -> Vec<f32> {
         let mut cells = vec![0.0; self.cells];
 
-        for food in foods {
-            let vec = food.position - position;
+        for target in targets {
+            let vec = target - position;
             let dist = vec.norm();
 
             if dist > self.fov_range {
@@ -68,9 +68,11 @@ mod tests {
     const TEST_EYE_CELLS: usize = 13;
 
     fn food(x: f32, y: f32) -> Food {
-        Food {
-            position: na::Point2::new(x, y),
-        }
+        Food::new(
+            &Config::default(),
+            na::Point2::new(x, y),
+            na::Vector2::zeros(),
+        )
     }
 
     struct TestCase {
@@ -90,7 +92,7 @@ mod tests {
             let actual = eye.process_vision(
                 na::Point2::new(self.x, self.y),
                 na::Rotation2::new(self.rot),
-                &self.foods,
+                self.foods.iter().map(|food| food.position),
             );
 
             let actual = actual
@@ -0,0 +1,388 @@
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Decides how good each animal is at the end of a generation - that's what
+/// the genetic algorithm then optimizes for.
+pub trait FitnessFunction: fmt::Debug + Send {
+    /// Scores given animals (all of the same species); the higher the score,
+    /// the better.
+    ///
+    /// Animals are scored all at once, because some functions (e.g. novelty)
+    /// compare them with each other.
+    fn evaluate(&mut self, config: &Config, animals: &[&Animal]) -> Vec<f32>;
+}
+
+impl dyn FitnessFunction {
+    crate fn from_config(config: &FitnessConfig) -> Box<Self> {
+        match config {
+            FitnessConfig::Nutrition => Box::new(NutritionFitness),
+            FitnessConfig::Energy => Box::new(EnergyFitness),
+            FitnessConfig::Distance => Box::new(DistanceFitness),
+            FitnessConfig::EnergyEfficiency => Box::new(EnergyEfficiencyFitness),
+            FitnessConfig::Survival => Box::new(SurvivalFitness),
+            FitnessConfig::Novelty(config) => Box::new(NoveltyFitness::new(config.clone())),
+
+            FitnessConfig::Weighted { terms } => Box::new(WeightedFitness::new(
+                terms
+                    .iter()
+                    .map(|term| (term.weight, Self::from_config(&term.fitness)))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// How much food an animal has eaten (poison doesn't make it negative).
+#[derive(Clone, Debug, Default)]
+pub struct NutritionFitness;
+
+impl FitnessFunction for NutritionFitness {
+    fn evaluate(&mut self, _: &Config, animals: &[&Animal]) -> Vec<f32> {
+        animals
+            .iter()
+            .map(|animal| animal.nutrition().max(0.0))
+            .collect()
+    }
+}
+
+/// Food, plus bonuses for surviving and for the energy left (weighted by
+/// `energy_fitness_survival` and `energy_fitness_energy`).
+#[derive(Clone, Debug, Default)]
+pub struct EnergyFitness;
+
+impl FitnessFunction for EnergyFitness {
+    fn evaluate(&mut self, config: &Config, animals: &[&Animal]) -> Vec<f32> {
+        animals
+            .iter()
+            .map(|animal| {
+                let energy = animal.energy() / config.energy_max;
+
+                animal.nutrition().max(0.0)
+                    + config.energy_fitness_survival * survival(config, animal)
+                    + config.energy_fitness_energy * energy
+            })
+            .collect()
+    }
+}
+
+/// How far an animal has travelled.
+#[derive(Clone, Debug, Default)]
+pub struct DistanceFitness;
+
+impl FitnessFunction for DistanceFitness {
+    fn evaluate(&mut self, _: &Config, animals: &[&Animal]) -> Vec<f32> {
+        animals.iter().map(|animal| animal.distance()).collect()
+    }
+}
+
+/// How much food an animal has eaten per each unit of energy it has burned.
+#[derive(Clone, Debug, Default)]
+pub struct EnergyEfficiencyFitness;
+
+impl FitnessFunction for EnergyEfficiencyFitness {
+    fn evaluate(&mut self, _: &Config, animals: &[&Animal]) -> Vec<f32> {
+        animals
+            .iter()
+            .map(|animal| {
+                if animal.energy_spent() > 0.0 {
+                    animal.nutrition().max(0.0) / animal.energy_spent()
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
+
+/// For how long (as a fraction of the generation) an animal has survived.
+#[derive(Clone, Debug, Default)]
+pub struct SurvivalFitness;
+
+impl FitnessFunction for SurvivalFitness {
+    fn evaluate(&mut self, config: &Config, animals: &[&Animal]) -> Vec<f32> {
+        animals
+            .iter()
+            .map(|animal| survival(config, animal))
+            .collect()
+    }
+}
+
+/// Novelty search: rewards animals for behaving differently than the rest of
+/// the population and than the animals from the previous generations.
+#[derive(Clone, Debug)]
+pub struct NoveltyFitness {
+    config: NoveltyConfig,
+    archives: HashMap<Species, NoveltyArchive>,
+}
+
+impl NoveltyFitness {
+    pub fn new(config: NoveltyConfig) -> Self {
+        assert!(config.k > 0);
+
+        Self {
+            config,
+            archives: Default::default(),
+        }
+    }
+
+    pub fn archive(&self, species: Species) -> Option<&NoveltyArchive> {
+        self.archives.get(&species)
+    }
+}
+
+impl FitnessFunction for NoveltyFitness {
+    fn evaluate(&mut self, _: &Config, animals: &[&Animal]) -> Vec<f32> {
+        let species = match animals.first() {
+            Some(animal) => animal.species(),
+            None => return Vec::new(),
+        };
+
+        let behaviors: Vec<_> = animals
+            .iter()
+            .map(|animal| self.config.behavior.describe(animal))
+            .collect();
+
+        let archive_size = self.config.archive_size;
+
+        let archive = self
+            .archives
+            .entry(species)
+            .or_insert_with(|| NoveltyArchive::new(archive_size));
+
+        let novelties = archive.novelty(&behaviors, self.config.k);
+
+        let mut most_novel: Vec<_> = (0..behaviors.len()).collect();
+
+        most_novel.sort_by(|&a, &b| {
+            novelties[b]
+                .partial_cmp(&novelties[a])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        for id in most_novel.into_iter().take(self.config.archive_additions) {
+            archive.insert(behaviors[id].clone());
+        }
+
+        novelties
+    }
+}
+
+/// Weighted sum of other fitness functions.
+#[derive(Debug)]
+pub struct WeightedFitness {
+    terms: Vec<(f32, Box<dyn FitnessFunction>)>,
+}
+
+impl WeightedFitness {
+    pub fn new(terms: Vec<(f32, Box<dyn FitnessFunction>)>) -> Self {
+        Self { terms }
+    }
+}
+
+impl FitnessFunction for WeightedFitness {
+    fn evaluate(&mut self, config: &Config, animals: &[&Animal]) -> Vec<f32> {
+        let mut fitnesses = vec![0.0; animals.len()];
+
+        for (weight, function) in &mut self.terms {
+            for (fitness, score) in fitnesses.iter_mut().zip(function.evaluate(config, animals)) {
+                *fitness += *weight * score;
+            }
+        }
+
+        fitnesses
+    }
+}
+
+fn survival(config: &Config, animal: &Animal) -> f32 {
+    (animal.lifetime() as f32 / config.sim_generation_length as f32).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn animals(config: &Config, count: usize) -> Vec<Animal> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        (0..count)
+            .map(|_| Animal::random(config, &mut rng, Species::Prey))
+            .collect()
+    }
+
+    fn evaluate(
+        function: &mut dyn FitnessFunction,
+        config: &Config,
+        animals: &[Animal],
+    ) -> Vec<f32> {
+        let animals: Vec<_> = animals.iter().collect();
+
+        function.evaluate(config, &animals)
+    }
+
+    mod nutrition {
+        use super::*;
+
+        #[test]
+        fn ignores_poison() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].nutrition = 2.0;
+            animals[1].nutrition = -1.0;
+
+            let actual = evaluate(&mut NutritionFitness, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [2.0, 0.0].as_ref());
+        }
+    }
+
+    mod energy {
+        use super::*;
+
+        #[test]
+        fn adds_bonuses_for_survival_and_energy() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].nutrition = 1.0;
+            animals[0].lifetime = config.sim_generation_length / 2;
+            animals[0].energy = config.energy_max / 2.0;
+
+            animals[1].nutrition = -1.0;
+            animals[1].lifetime = config.sim_generation_length;
+            animals[1].energy = 0.0;
+
+            let actual = evaluate(&mut EnergyFitness, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [2.0, 1.0].as_ref());
+        }
+    }
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn returns_distance_travelled() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].distance = 0.5;
+            animals[1].distance = 2.0;
+
+            let actual = evaluate(&mut DistanceFitness, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [0.5, 2.0].as_ref());
+        }
+    }
+
+    mod energy_efficiency {
+        use super::*;
+
+        #[test]
+        fn divides_food_by_energy_spent() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].nutrition = 2.0;
+            animals[0].energy_spent = 0.5;
+
+            let actual = evaluate(&mut EnergyEfficiencyFitness, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [4.0, 0.0].as_ref());
+        }
+    }
+
+    mod survival {
+        use super::*;
+
+        #[test]
+        fn is_capped_at_entire_generation() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].lifetime = config.sim_generation_length / 4;
+            animals[1].lifetime = config.sim_generation_length * 2;
+
+            let actual = evaluate(&mut SurvivalFitness, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [0.25, 1.0].as_ref());
+        }
+    }
+
+    mod novelty {
+        use super::*;
+
+        #[test]
+        fn rewards_distinct_behaviors() {
+            let config = Config::default();
+            let mut animals = animals(&config, 3);
+
+            for (animal, (x, y)) in animals
+                .iter_mut()
+                .zip(vec![(0.1, 0.1), (0.1, 0.2), (0.9, 0.2)])
+            {
+                animal.position = na::Point2::new(x, y);
+                animal.trail = vec![animal.position];
+            }
+
+            let mut function = NoveltyFitness::new(NoveltyConfig {
+                k: 1,
+                archive_additions: 1,
+                ..Default::default()
+            });
+
+            let actual = evaluate(&mut function, &config, &animals);
+
+            // Both endpoints are the same, hence the `SQRT_2`
+            approx::assert_relative_eq!(
+                actual.as_slice(),
+                [0.1 * SQRT_2, 0.1 * SQRT_2, 0.8 * SQRT_2].as_ref(),
+                epsilon = 1e-6
+            );
+
+            let archive = function.archive(Species::Prey).unwrap();
+
+            assert_eq!(archive.len(), 1);
+
+            approx::assert_relative_eq!(
+                archive.behaviors().next().unwrap(),
+                [0.9, 0.2, 0.9, 0.2].as_ref()
+            );
+        }
+    }
+
+    mod weighted {
+        use super::*;
+
+        #[test]
+        fn sums_weighted_terms() {
+            let config = Config::default();
+            let mut animals = animals(&config, 2);
+
+            animals[0].nutrition = 3.0;
+            animals[0].distance = 0.5;
+            animals[1].nutrition = -1.0;
+            animals[1].distance = 2.0;
+
+            let mut function = <dyn FitnessFunction>::from_config(&FitnessConfig::Weighted {
+                terms: vec![
+                    WeightedFitnessConfig {
+                        weight: 1.0,
+                        fitness: FitnessConfig::Nutrition,
+                    },
+                    WeightedFitnessConfig {
+                        weight: 2.0,
+                        fitness: FitnessConfig::Distance,
+                    },
+                ],
+            });
+
+            let actual = evaluate(&mut *function, &config, &animals);
+
+            approx::assert_relative_eq!(actual.as_slice(), [4.0, 4.0].as_ref());
+        }
+    }
+}
@@ -1,20 +1,209 @@
 use crate::*;
+use rand::distributions::{Distribution, WeightedIndex};
 
 #[derive(Debug)]
 pub struct Food {
     crate position: na::Point2<f32>,
+    crate velocity: na::Vector2<f32>,
+
+    /// Index into `Config::food_kinds`
+    crate kind: usize,
+    crate size: f32,
+    crate nutrition: f32,
+
+    /// Number of steps left until this food grows back after being eaten
+    crate regrowth: usize,
+
+    /// Whether this food has been eaten for good (until the next generation)
+    crate depleted: bool,
+
+    /// Whether this food is currently out of season
+    crate dormant: bool,
 }
 
 impl Food {
     pub fn position(&self) -> na::Point2<f32> {
         self.position
     }
+
+    pub fn kind(&self) -> usize {
+        self.kind
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn nutrition(&self) -> f32 {
+        self.nutrition
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.regrowth == 0 && !self.depleted && !self.dormant
+    }
 }
 
 impl Food {
-    crate fn random(rng: &mut dyn RngCore) -> Self {
-        Self {
-            position: rng.gen(),
+    crate fn new(config: &Config, position: na::Point2<f32>, velocity: na::Vector2<f32>) -> Self {
+        let mut this = Self {
+            position,
+            velocity,
+            kind: 0,
+            size: 0.0,
+            nutrition: 0.0,
+            regrowth: 0,
+            depleted: false,
+            dormant: false,
+        };
+
+        this.set_kind(config, 0);
+        this
+    }
+
+    crate fn random(config: &Config, spawner: &dyn FoodSpawner, rng: &mut dyn RngCore) -> Self {
+        let position = spawner.spawn(rng);
+
+        let velocity = if config.food_speed > 0.0 {
+            rng.gen::<na::Rotation2<f32>>() * na::Vector2::new(config.food_speed, 0.0)
+        } else {
+            na::Vector2::zeros()
+        };
+
+        let mut this = Self::new(config, position, velocity);
+
+        this.roll_kind(config, rng);
+        this
+    }
+
+    /// Makes this food grow back (after `food_regrowth` steps) somewhere
+    /// else - or, for finite food, never again in this generation.
+    crate fn regrow(&mut self, config: &Config, spawner: &dyn FoodSpawner, rng: &mut dyn RngCore) {
+        self.position = spawner.spawn(rng);
+        self.roll_kind(config, rng);
+
+        if config.food_finite {
+            self.depleted = true;
+        } else {
+            self.regrowth = config.food_regrowth;
+        }
+    }
+
+    crate fn replenish(
+        &mut self,
+        config: &Config,
+        spawner: &dyn FoodSpawner,
+        rng: &mut dyn RngCore,
+    ) {
+        self.position = spawner.spawn(rng);
+        self.roll_kind(config, rng);
+        self.regrowth = 0;
+        self.depleted = false;
+    }
+
+    crate fn process_movement(&mut self) {
+        self.regrowth = self.regrowth.saturating_sub(1);
+        self.position += self.velocity;
+        self.position.x = na::wrap(self.position.x, 0.0, 1.0);
+        self.position.y = na::wrap(self.position.y, 0.0, 1.0);
+    }
+}
+
+impl Food {
+    /// Picks kind of this food according to `FoodKind::frequency`.
+    fn roll_kind(&mut self, config: &Config, rng: &mut dyn RngCore) {
+        if config.food_kinds.len() < 2 {
+            return;
+        }
+
+        let frequencies = config.food_kinds.iter().map(|kind| kind.frequency);
+
+        let kind = WeightedIndex::new(frequencies)
+            .expect("got invalid food frequencies")
+            .sample(rng);
+
+        self.set_kind(config, kind);
+    }
+
+    fn set_kind(&mut self, config: &Config, kind: usize) {
+        self.kind = kind;
+        self.size = config.food_size * config.food_kinds[kind].size;
+        self.nutrition = config.food_kinds[kind].nutrition;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn food(config: &Config) -> Food {
+        Food::new(config, na::Point2::new(0.5, 0.5), na::Vector2::zeros())
+    }
+
+    mod regrow {
+        use super::*;
+
+        #[test]
+        fn waits_for_regrowth() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                food_regrowth: 2,
+                ..Default::default()
+            };
+
+            let mut food = food(&config);
+
+            food.regrow(&config, &UniformFoodSpawner, &mut rng);
+            assert!(!food.is_available());
+
+            food.process_movement();
+            assert!(!food.is_available());
+
+            food.process_movement();
+            assert!(food.is_available());
+        }
+
+        #[test]
+        fn depletes_finite_food_until_replenished() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let config = Config {
+                food_finite: true,
+                ..Default::default()
+            };
+
+            let mut food = food(&config);
+
+            food.regrow(&config, &UniformFoodSpawner, &mut rng);
+
+            for _ in 0..10 {
+                food.process_movement();
+            }
+
+            assert!(!food.is_available());
+
+            food.replenish(&config, &UniformFoodSpawner, &mut rng);
+            assert!(food.is_available());
+        }
+    }
+
+    mod process_movement {
+        use super::*;
+
+        #[test]
+        fn moves_food_around_the_world() {
+            let mut food = Food::new(
+                &Config::default(),
+                na::Point2::new(0.95, 0.5),
+                na::Vector2::new(0.1, -0.2),
+            );
+
+            food.process_movement();
+
+            approx::assert_relative_eq!(food.position.x, 0.05, epsilon = 1e-6);
+            approx::assert_relative_eq!(food.position.y, 0.3, epsilon = 1e-6);
         }
     }
 }
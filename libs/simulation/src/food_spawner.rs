@@ -0,0 +1,131 @@
+use crate::*;
+use std::fmt;
+
+/// Decides where new food grows.
+pub trait FoodSpawner: fmt::Debug + Send {
+    /// Called at the beginning of each generation, before any food is
+    /// spawned.
+    fn reset(&mut self, _rng: &mut dyn RngCore) {
+        //
+    }
+
+    fn spawn(&self, rng: &mut dyn RngCore) -> na::Point2<f32>;
+}
+
+impl dyn FoodSpawner {
+    crate fn from_config(config: &Config) -> Box<Self> {
+        match config.food_spawner {
+            FoodSpawnerConfig::Uniform => Box::new(UniformFoodSpawner),
+            FoodSpawnerConfig::Clustered => Box::new(ClusteredFoodSpawner::new(
+                config.food_clusters,
+                config.food_cluster_radius,
+            )),
+        }
+    }
+}
+
+/// Spreads food evenly across the entire world.
+#[derive(Clone, Debug, Default)]
+pub struct UniformFoodSpawner;
+
+impl FoodSpawner for UniformFoodSpawner {
+    fn spawn(&self, rng: &mut dyn RngCore) -> na::Point2<f32> {
+        rng.gen()
+    }
+}
+
+/// Grows food in a few circular patches that move to new, random places
+/// every generation.
+#[derive(Clone, Debug)]
+pub struct ClusteredFoodSpawner {
+    clusters: usize,
+    radius: f32,
+    centers: Vec<na::Point2<f32>>,
+}
+
+impl ClusteredFoodSpawner {
+    pub fn new(clusters: usize, radius: f32) -> Self {
+        assert!(clusters > 0);
+        assert!(radius >= 0.0);
+
+        Self {
+            clusters,
+            radius,
+            centers: Vec::new(),
+        }
+    }
+}
+
+impl FoodSpawner for ClusteredFoodSpawner {
+    fn reset(&mut self, rng: &mut dyn RngCore) {
+        self.centers = (0..self.clusters).map(|_| rng.gen()).collect();
+    }
+
+    fn spawn(&self, rng: &mut dyn RngCore) -> na::Point2<f32> {
+        assert!(!self.centers.is_empty(), "spawner hasn't been reset yet");
+
+        let center = self.centers[rng.gen_range(0..self.centers.len())];
+
+        // Square root makes the points spread evenly over the circle's area
+        // instead of gathering around its center
+        let distance = self.radius * rng.gen::<f32>().sqrt();
+        let angle = rng.gen_range(0.0..(2.0 * PI));
+        let position = center + na::Rotation2::new(angle) * na::Vector2::new(distance, 0.0);
+
+        na::Point2::new(
+            na::wrap(position.x, 0.0, 1.0),
+            na::wrap(position.y, 0.0, 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod clustered {
+        use super::*;
+
+        #[test]
+        fn spawns_food_around_cluster_centers() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut spawner = ClusteredFoodSpawner::new(2, 0.05);
+
+            spawner.reset(&mut rng);
+
+            for _ in 0..100 {
+                let position = spawner.spawn(&mut rng);
+
+                let distance = spawner
+                    .centers
+                    .iter()
+                    .map(|center| {
+                        // Food can wrap around the world's edges, so let's
+                        // check the toroidal distance
+                        let dx = (position.x - center.x).abs();
+                        let dy = (position.y - center.y).abs();
+
+                        dx.min(1.0 - dx).hypot(dy.min(1.0 - dy))
+                    })
+                    .fold(f32::INFINITY, f32::min);
+
+                assert!(distance <= 0.05 + 1e-5);
+            }
+        }
+
+        #[test]
+        fn moves_clusters_on_reset() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut spawner = ClusteredFoodSpawner::new(3, 0.1);
+
+            spawner.reset(&mut rng);
+            let centers = spawner.centers.clone();
+
+            spawner.reset(&mut rng);
+            assert_eq!(spawner.centers.len(), 3);
+            assert_ne!(spawner.centers, centers);
+        }
+    }
+}
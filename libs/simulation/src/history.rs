@@ -0,0 +1,126 @@
+use crate::*;
+use std::fmt::Write;
+
+/// Statistics of all the generations a simulation has gone through, for
+/// plotting learning curves.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct History {
+    generations: Vec<Statistics>,
+}
+
+impl History {
+    const CSV_HEADER: &'static str = "generation,\
+        min_fitness,max_fitness,avg_fitness,median_fitness,species,\
+        predators_min_fitness,predators_max_fitness,predators_avg_fitness,\
+        predators_median_fitness,predators_species";
+
+    /// Statistics of each generation, oldest first.
+    pub fn generations(&self) -> &[Statistics] {
+        &self.generations
+    }
+
+    pub fn last(&self) -> Option<&Statistics> {
+        self.generations.last()
+    }
+
+    /// Exports history as CSV, one generation per row; columns related to
+    /// predators are left empty in generations without predators.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+
+        writeln!(csv, "{}", Self::CSV_HEADER).unwrap();
+
+        for stats in &self.generations {
+            write!(csv, "{},", stats.generation).unwrap();
+            write_csv_statistics(&mut csv, Some(&stats.ga));
+            csv.push(',');
+            write_csv_statistics(&mut csv, stats.predators.as_ref());
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Exports history as a JSON array, one object per generation.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    crate fn push(&mut self, stats: Statistics) {
+        self.generations.push(stats);
+    }
+}
+
+fn write_csv_statistics(csv: &mut String, stats: Option<&ga::Statistics>) {
+    if let Some(stats) = stats {
+        write!(
+            csv,
+            "{},{},{},{},{}",
+            stats.min_fitness(),
+            stats.max_fitness(),
+            stats.avg_fitness(),
+            stats.median_fitness(),
+            stats.species()
+        )
+        .unwrap();
+    } else {
+        csv.push_str(",,,,");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> History {
+        let mut sim = Simulation::new(
+            Config {
+                world_animals: 4,
+                world_predators: 2,
+                sim_generation_length: 10,
+                ..Default::default()
+            },
+            0,
+        );
+
+        sim.train();
+        sim.train();
+        sim.history().clone()
+    }
+
+    #[test]
+    fn to_csv() {
+        let history = history();
+        let csv = history.to_csv();
+        let rows: Vec<_> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], History::CSV_HEADER);
+
+        for (row, stats) in rows[1..].iter().zip(history.generations()) {
+            let cells: Vec<_> = row.split(',').collect();
+
+            assert_eq!(cells.len(), 11);
+            assert_eq!(cells[0], stats.generation.to_string());
+            assert_eq!(cells[2], stats.ga.max_fitness().to_string());
+            assert_eq!(cells[5], "1");
+            assert_eq!(cells[10], "1");
+        }
+    }
+
+    #[test]
+    fn to_json() {
+        let history = history();
+        let json: serde_json::Value = serde_json::from_str(&history.to_json()).unwrap();
+        let generations = json.as_array().unwrap();
+
+        assert_eq!(generations.len(), 2);
+        assert_eq!(generations[1]["generation"], 1);
+        assert_eq!(
+            generations[1]["ga"]["avg_fitness"].as_f64().unwrap() as f32,
+            history.generations()[1].ga.avg_fitness()
+        );
+        assert_eq!(generations[1]["predators"]["species"], 1);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::*;
+use std::thread;
+
+/// Island model: a few independent simulations evolving in parallel, which
+/// every now and then exchange their best animals.
+///
+/// Migration follows a ring - the champions of each island move to the next
+/// one, replacing random animals there - which keeps the islands diverse
+/// while still letting good solutions spread.
+pub struct Islands {
+    islands: Vec<Simulation>,
+    migration_interval: usize,
+    migrants: usize,
+}
+
+impl Islands {
+    /// Creates one island per config; `seed` determines seeds of all the
+    /// islands, so the whole run is reproducible.
+    ///
+    /// Panics if any of the parameters is invalid - see `Islands::try_new()`.
+    pub fn new(
+        configs: Vec<Config>,
+        seed: u64,
+        migration_interval: usize,
+        migrants: usize,
+    ) -> Self {
+        match Self::try_new(configs, seed, migration_interval, migrants) {
+            Ok(this) => this,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Islands can have different configs, but their animals must have the
+    /// same brains (the same number of eye cells, food kinds, neurons etc.),
+    /// so that they can migrate.
+    pub fn try_new(
+        configs: Vec<Config>,
+        seed: u64,
+        migration_interval: usize,
+        migrants: usize,
+    ) -> Result<Self, ConfigError> {
+        let mut err = ConfigError { fields: Vec::new() };
+        let mut islands = Vec::new();
+
+        err.check(
+            "configs",
+            configs.len(),
+            !configs.is_empty(),
+            "at least one config",
+        );
+
+        err.check(
+            "migration_interval",
+            migration_interval,
+            migration_interval >= 1,
+            ">= 1",
+        );
+
+        for (id, config) in configs.into_iter().enumerate() {
+            match Simulation::try_new(config, seed.wrapping_add(id as u64)) {
+                Ok(sim) => islands.push(sim),
+
+                Err(island_err) => {
+                    err.fields
+                        .extend(island_err.fields.into_iter().map(|mut field| {
+                            field.field = format!("configs[{}].{}", id, field.field);
+                            field
+                        }));
+                }
+            }
+        }
+
+        if err.fields.is_empty() {
+            for (id, sim) in islands.iter().enumerate().skip(1) {
+                for &species in &Species::ALL {
+                    let expected = Brain::layers(islands[0].config(), species);
+                    let actual = Brain::layers(sim.config(), species);
+
+                    err.check(
+                        format!("configs[{}]", id),
+                        format!("{:?} brain with layers {:?}", species, actual),
+                        actual == expected,
+                        format!("layers {:?}, as in configs[0]", expected),
+                    );
+                }
+            }
+        }
+
+        if !err.fields.is_empty() {
+            return Err(err);
+        }
+
+        Ok(Self {
+            islands,
+            migration_interval,
+            migrants,
+        })
+    }
+
+    pub fn islands(&self) -> impl Iterator<Item = &Simulation> {
+        self.islands.iter()
+    }
+
+    /// Trains all the islands (each on its own thread) for
+    /// `migration_interval` generations and then migrates their champions.
+    ///
+    /// Returns statistics for each island and each generation.
+    pub fn train(&mut self) -> Vec<Vec<Statistics>> {
+        self.train_for(self.migration_interval)
+    }
+
+    /// Same as `Islands::train()`, but for given number of generations -
+    /// e.g. to finish a run which isn't a multiple of `migration_interval`.
+    pub fn train_for(&mut self, generations: usize) -> Vec<Vec<Statistics>> {
+        let threads: Vec<_> = self
+            .islands
+            .drain(..)
+            .map(|mut sim| {
+                thread::spawn(move || {
+                    let stats = (0..generations).map(|_| sim.train()).collect::<Vec<_>>();
+
+                    (sim, stats)
+                })
+            })
+            .collect();
+
+        let (islands, stats) = threads
+            .into_iter()
+            .map(|thread| thread.join().expect("island's thread has panicked"))
+            .unzip();
+
+        self.islands = islands;
+        self.migrate();
+
+        stats
+    }
+}
+
+impl Islands {
+    fn migrate(&mut self) {
+        if self.islands.len() < 2 || self.migrants == 0 {
+            return;
+        }
+
+        let emigrants: Vec<Vec<_>> = self
+            .islands
+            .iter()
+            .map(|sim| {
+                Species::ALL
+                    .iter()
+                    .flat_map(|&species| sim.champions(species).take(self.migrants))
+                    .cloned()
+                    .collect()
+            })
+            .collect();
+
+        let len = self.islands.len();
+
+        for (id, emigrants) in emigrants.into_iter().enumerate() {
+            self.islands[(id + 1) % len].immigrate(emigrants);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            world_animals: 4,
+            world_foods: 4,
+            sim_generation_length: 10,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn trains_all_islands() {
+        let mut islands = Islands::new(vec![config(), config(), config()], 0, 2, 1);
+        let stats = islands.train();
+
+        assert_eq!(stats.len(), 3);
+        assert!(stats.iter().all(|stats| stats.len() == 2));
+        assert!(islands.islands().all(|sim| sim.generation() == 2));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let err = Islands::try_new(
+            vec![
+                config(),
+                Config {
+                    eye_cells: 0,
+                    ..config()
+                },
+            ],
+            0,
+            1,
+            1,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(err.fields.len(), 1);
+        assert_eq!(err.fields[0].field, "configs[1].eye_cells");
+    }
+
+    #[test]
+    fn rejects_missing_islands_and_migrations() {
+        let err = Islands::try_new(Vec::new(), 0, 0, 1).err().unwrap();
+
+        let fields: Vec<_> = err.fields.iter().map(|field| &field.field).collect();
+
+        assert_eq!(fields, ["configs", "migration_interval"]);
+    }
+
+    #[test]
+    fn rejects_islands_with_different_brains() {
+        let err = Islands::try_new(
+            vec![
+                config(),
+                Config {
+                    brain_neurons: 3,
+                    ..config()
+                },
+            ],
+            0,
+            1,
+            1,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(err.fields.len(), 2);
+        assert!(err.fields.iter().all(|field| field.field == "configs[1]"));
+    }
+
+    #[test]
+    fn accepts_islands_with_different_worlds() {
+        let mut islands = Islands::try_new(
+            vec![
+                config(),
+                Config {
+                    world_animals: 6,
+                    food_size: 0.02,
+                    ..config()
+                },
+            ],
+            0,
+            1,
+            1,
+        )
+        .unwrap();
+
+        islands.train();
+        islands.train();
+
+        assert!(islands.islands().all(|sim| sim.generation() == 2));
+    }
+
+    #[test]
+    fn trains_for_given_number_of_generations() {
+        let mut islands = Islands::new(vec![config(), config()], 0, 4, 1);
+        let stats = islands.train_for(1);
+
+        assert!(stats.iter().all(|stats| stats.len() == 1));
+        assert!(islands.islands().all(|sim| sim.generation() == 1));
+    }
+
+    #[test]
+    fn migrates_champions_to_the_next_island() {
+        let mut islands = Islands::new(vec![config(), config()], 0, 1, 1);
+
+        islands.train();
+
+        for (id, sim) in islands.islands.iter().enumerate() {
+            let champion = islands.islands[(id + 1) % 2]
+                .champions(Species::Prey)
+                .next()
+                .unwrap();
+
+            let migrated = sim.world.animals.iter().any(|animal| {
+                let actual = AnimalCheckpoint::from(animal);
+
+                actual
+                    .genes
+                    .iter()
+                    .zip(&champion.genes)
+                    .all(|(a, b)| (a - b).abs() < 1e-6)
+            });
+
+            assert!(migrated);
+        }
+    }
+}
@@ -1,13 +1,27 @@
 #![feature(crate_visibility_modifier)]
 
-pub use self::{animal::*, brain::*, config::*, eye::*, food::*, statistics::*, world::*};
+pub use self::{
+    animal::*, brain::*, checkpoint::*, config::*, config_error::*, eye::*, fitness_function::*,
+    food::*, food_spawner::*, history::*, islands::*, novelty_archive::*, observer::*, species::*,
+    statistics::*, world::*,
+};
 
 mod animal;
 mod animal_individual;
+mod behavior;
 mod brain;
+mod checkpoint;
 mod config;
+mod config_error;
 mod eye;
+mod fitness_function;
 mod food;
+mod food_spawner;
+mod history;
+mod islands;
+mod novelty_archive;
+mod observer;
+mod species;
 mod statistics;
 mod world;
 
@@ -15,29 +29,170 @@ use self::animal_individual::*;
 use lib_genetic_algorithm as ga;
 use lib_neural_network as nn;
 use nalgebra as na;
-use rand::{Rng, RngCore};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::f32::consts::*;
 
 pub struct Simulation {
     config: Config,
     world: World,
+    food_spawner: Box<dyn FoodSpawner>,
+    fitness_function: Box<dyn FitnessFunction>,
+    objectives: Vec<Box<dyn FitnessFunction>>,
+    champions: Vec<AnimalCheckpoint>,
+    map_elites: HashMap<Species, ga::MapElites>,
+    optimizers: HashMap<Species, Box<dyn ga::Optimizer<AnimalIndividual> + Send>>,
+    observers: Vec<Box<dyn Observer>>,
+    history: History,
+    rng: ChaCha8Rng,
+    seed: u64,
     age: usize,
     generation: usize,
 }
 
 impl Simulation {
-    pub fn random(config: Config, rng: &mut dyn RngCore) -> Self {
-        let world = World::random(&config, rng);
+    /// Creates a new simulation; the same config and the same seed always
+    /// yield the same run, no matter the platform.
+    ///
+    /// Panics if the config is invalid - see `Simulation::try_new()`.
+    pub fn new(config: Config, seed: u64) -> Self {
+        match Self::try_new(config, seed) {
+            Ok(this) => this,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn try_new(config: Config, seed: u64) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let config = config.upgrade();
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut food_spawner = <dyn FoodSpawner>::from_config(&config);
+
+        food_spawner.reset(&mut rng);
+
+        let world = World::random(&config, &*food_spawner, &mut rng);
+        let fitness_function = <dyn FitnessFunction>::from_config(&config.fitness);
 
-        Self {
+        let objectives = match &config.evolution {
+            EvolutionConfig::Nsga2 { objectives } => objectives
+                .iter()
+                .map(<dyn FitnessFunction>::from_config)
+                .collect(),
+
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
             config,
             world,
+            food_spawner,
+            fitness_function,
+            objectives,
+            champions: Vec::new(),
+            map_elites: HashMap::new(),
+            optimizers: HashMap::new(),
+            observers: Vec::new(),
+            history: History::default(),
+            rng,
+            seed,
             age: 0,
             generation: 0,
+        })
+    }
+
+    /// Restores population saved through `Simulation::checkpoint()`.
+    ///
+    /// Panics if the checkpoint is invalid - see
+    /// `Simulation::try_from_checkpoint()`.
+    pub fn from_checkpoint(checkpoint: Checkpoint, seed: u64) -> Self {
+        match Self::try_from_checkpoint(checkpoint, seed) {
+            Ok(this) => this,
+            Err(err) => panic!("{}", err),
         }
     }
 
+    /// Fails when checkpoint's config is invalid or when genes of its animals
+    /// don't fit the brains described by that config (e.g. because the
+    /// checkpoint has been edited by hand).
+    ///
+    /// Also fails for kinds of evolution whose state isn't saved in
+    /// checkpoints - see `Checkpoint`.
+    pub fn try_from_checkpoint(checkpoint: Checkpoint, seed: u64) -> Result<Self, ConfigError> {
+        let mut this = Self::try_new(checkpoint.config, seed)?;
+        let mut err = ConfigError { fields: Vec::new() };
+
+        err.check(
+            "config.evolution.type",
+            this.config.evolution.name(),
+            !this.config.evolution.keeps_state(),
+            "generational or mu_lambda, since checkpoints don't save state of the other kinds",
+        );
+
+        for (id, animal) in checkpoint.animals.iter().enumerate() {
+            let genes = Brain::genes(&this.config, animal.species);
+
+            err.check(
+                format!("animals[{}].genes", id),
+                format!("{} genes", animal.genes.len()),
+                animal.genes.len() == genes,
+                format!("{} genes", genes),
+            );
+        }
+
+        if !err.fields.is_empty() {
+            return Err(err);
+        }
+
+        let animals = checkpoint.animals;
+
+        this.generation = checkpoint.generation;
+
+        this.with_rng(|this, rng| {
+            this.world.animals = animals
+                .into_iter()
+                .map(|animal| {
+                    let chromosome = animal.genes.into_iter().collect();
+                    Animal::from_chromosome(&this.config, rng, animal.species, chromosome)
+                })
+                .collect();
+        });
+
+        Ok(this)
+    }
+
+    /// Replaces the food spawner chosen through `Config::food_spawner` with a
+    /// custom one, regrowing all the food according to it.
+    pub fn set_food_spawner(&mut self, food_spawner: impl FoodSpawner + 'static) {
+        self.food_spawner = Box::new(food_spawner);
+
+        self.with_rng(|this, rng| {
+            this.food_spawner.reset(rng);
+
+            for food in &mut this.world.foods {
+                food.replenish(&this.config, &*this.food_spawner, rng);
+            }
+        });
+    }
+
+    /// Replaces the fitness function chosen through `Config::fitness` with a
+    /// custom one.
+    pub fn set_fitness_function(&mut self, fitness_function: impl FitnessFunction + 'static) {
+        self.fitness_function = Box::new(fitness_function);
+    }
+
+    /// Registers an observer that will get notified about everything that
+    /// happens from now on; observers are called in the order of their
+    /// registration.
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -46,39 +201,269 @@ impl Simulation {
         &self.world
     }
 
-    pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
-        self.process_collisions(rng);
-        self.process_brains();
-        self.process_movements();
-        self.try_evolving(rng)
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Statistics of all the generations simulated so far (not including
+    /// the ones before the checkpoint this simulation was restored from).
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Animals of given species from the previous generation, starting with
+    /// the fittest one.
+    pub fn champions(&self, species: Species) -> impl Iterator<Item = &AnimalCheckpoint> {
+        self.champions
+            .iter()
+            .filter(move |animal| animal.species == species)
+    }
+
+    /// Elites of given species found so far, when evolving through
+    /// `EvolutionConfig::MapElites`.
+    pub fn elites(&self, species: Species) -> impl Iterator<Item = &ga::Elite> {
+        self.map_elites
+            .get(&species)
+            .into_iter()
+            .flat_map(|map_elites| map_elites.elites())
+    }
+
+    /// Replaces a random animal from the current generation with the elite
+    /// from given cell; returns whether there was such an elite.
+    pub fn spawn_elite(&mut self, species: Species, cell: &[usize]) -> bool {
+        let elite = self
+            .map_elites
+            .get(&species)
+            .and_then(|map_elites| map_elites.elite(cell));
+
+        if let Some(elite) = elite {
+            let immigrant = AnimalCheckpoint {
+                species,
+                genes: elite.chromosome().iter().copied().collect(),
+            };
+
+            self.immigrate(Some(immigrant));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replaces random animals from the current generation with given ones
+    /// (e.g. champions of another simulation).
+    pub fn immigrate(&mut self, immigrants: impl IntoIterator<Item = AnimalCheckpoint>) {
+        self.with_rng(|this, rng| {
+            let mut slots: Vec<_> = (0..this.world.animals.len()).collect();
+
+            slots.shuffle(rng);
+
+            for immigrant in immigrants {
+                let slot = slots
+                    .iter()
+                    .position(|&slot| this.world.animals[slot].species == immigrant.species);
+
+                if let Some(slot) = slot {
+                    let slot = slots.swap_remove(slot);
+                    let chromosome = immigrant.genes.into_iter().collect();
+
+                    this.world.animals[slot] =
+                        Animal::from_chromosome(&this.config, rng, immigrant.species, chromosome);
+                }
+            }
+        });
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            generation: self.generation,
+            config: self.config.clone(),
+            animals: self
+                .world
+                .animals
+                .iter()
+                .map(AnimalCheckpoint::from)
+                .collect(),
+        }
+    }
+
+    pub fn step(&mut self) -> Option<Statistics> {
+        for observer in &mut self.observers {
+            observer.on_step_start(&self.world);
+        }
+
+        let statistics = self.with_rng(|this, rng| {
+            let alive = this.alive_animals();
+
+            this.process_seasons();
+            this.process_collisions(rng);
+            this.process_brains();
+            this.process_movements();
+            this.report_deaths(&alive);
+
+            if this.config.sim_continuous {
+                this.process_births_and_deaths(rng);
+            }
+
+            this.try_evolving(rng)
+        });
+
+        if let Some(statistics) = &statistics {
+            self.history.push(statistics.clone());
+        }
+
+        for observer in &mut self.observers {
+            if let Some(statistics) = &statistics {
+                observer.on_generation_evolved(statistics);
+            }
+
+            observer.on_step_end(&self.world);
+        }
+
+        statistics
     }
 
-    pub fn train(&mut self, rng: &mut dyn RngCore) -> Statistics {
+    pub fn train(&mut self) -> Statistics {
         loop {
-            if let Some(statistics) = self.step(rng) {
+            if let Some(statistics) = self.step() {
                 return statistics;
             }
         }
     }
 }
 
+/// Lets all the animals look around and think; since each animal only reads
+/// the world and then updates itself, the outcome doesn't depend on the order
+/// in which the animals get processed - so the same code works both with
+/// regular and with parallel iterators.
+macro_rules! process_brains {
+    ($this:expr, $iter:ident, $iter_mut:ident) => {{
+        let config = &$this.config;
+        let world = &$this.world;
+
+        let visions: Vec<_> = world
+            .animals
+            .$iter()
+            .map(|animal| animal.process_vision(config, world))
+            .collect();
+
+        $this
+            .world
+            .animals
+            .$iter_mut()
+            .zip(visions)
+            .for_each(|(animal, vision)| animal.process_brain(config, vision));
+    }};
+}
+
 impl Simulation {
+    /// Lends simulation's rng to given function - the rng has to be moved out
+    /// for that time (leaving a placeholder behind, which is cheap to create,
+    /// since ChaCha doesn't generate anything until it's used), so that the
+    /// function can borrow the simulation too.
+    fn with_rng<T>(&mut self, f: impl FnOnce(&mut Self, &mut dyn RngCore) -> T) -> T {
+        let placeholder = ChaCha8Rng::from_seed(Default::default());
+        let mut rng = std::mem::replace(&mut self.rng, placeholder);
+        let result = f(self, &mut rng);
+
+        self.rng = rng;
+        result
+    }
+
     fn process_collisions(&mut self, rng: &mut dyn RngCore) {
         for animal in &mut self.world.animals {
+            if !animal.is_alive() || animal.species != Species::Prey {
+                continue;
+            }
+
             for food in &mut self.world.foods {
+                if !food.is_available() {
+                    continue;
+                }
+
                 let distance = na::distance(&animal.position, &food.position);
 
+                if distance <= food.size {
+                    animal.eat(&self.config, food.nutrition);
+
+                    for observer in &mut self.observers {
+                        observer.on_food_eaten(animal, food);
+                    }
+
+                    food.regrow(&self.config, &*self.food_spawner, rng);
+                }
+            }
+        }
+
+        self.process_hunting();
+    }
+
+    fn process_hunting(&mut self) {
+        let animals = &mut self.world.animals;
+
+        for predator_id in 0..animals.len() {
+            if animals[predator_id].species != Species::Predator || !animals[predator_id].is_alive()
+            {
+                continue;
+            }
+
+            for prey_id in 0..animals.len() {
+                if animals[prey_id].species != Species::Prey || !animals[prey_id].is_alive() {
+                    continue;
+                }
+
+                let distance =
+                    na::distance(&animals[predator_id].position, &animals[prey_id].position);
+
                 if distance <= self.config.food_size {
-                    animal.satiation += 1;
-                    food.position = rng.gen();
+                    animals[prey_id].die();
+                    animals[predator_id].eat(&self.config, 1.0);
                 }
             }
         }
     }
 
     fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.process_brain(&self.config, &self.world.foods);
+        #[cfg(not(feature = "parallel"))]
+        self.process_brains_sequentially();
+
+        #[cfg(feature = "parallel")]
+        self.process_brains_in_parallel();
+    }
+
+    #[cfg_attr(all(feature = "parallel", not(test)), allow(dead_code))]
+    fn process_brains_sequentially(&mut self) {
+        process_brains!(self, iter, iter_mut);
+    }
+
+    #[cfg(feature = "parallel")]
+    fn process_brains_in_parallel(&mut self) {
+        use rayon::prelude::*;
+
+        process_brains!(self, par_iter, par_iter_mut);
+    }
+
+    /// Returns which animals are alive, so that `report_deaths()` can later
+    /// tell which ones have died in the meantime; there's nothing to compare
+    /// when nobody's observing.
+    fn alive_animals(&self) -> Vec<bool> {
+        if self.observers.is_empty() {
+            Vec::new()
+        } else {
+            self.world.animals.iter().map(Animal::is_alive).collect()
+        }
+    }
+
+    fn report_deaths(&mut self, alive: &[bool]) {
+        for (animal, &was_alive) in self.world.animals.iter().zip(alive) {
+            if was_alive && !animal.is_alive() {
+                for observer in &mut self.observers {
+                    observer.on_animal_died(animal);
+                }
+            }
         }
     }
 
@@ -86,63 +471,365 @@ impl Simulation {
         for animal in &mut self.world.animals {
             animal.process_movement();
         }
+
+        for food in &mut self.world.foods {
+            food.process_movement();
+        }
+    }
+
+    /// Seasons make some of the food disappear for a while - the amount of
+    /// available food goes down from 100% at the beginning of each season to
+    /// `1.0 - food_season_amplitude` in its middle, and then back up.
+    fn process_seasons(&mut self) {
+        if self.config.food_season_length == 0 {
+            return;
+        }
+
+        let time = self.generation * (self.config.sim_generation_length + 1) + self.age;
+        let season = (time % self.config.food_season_length) as f32;
+        let phase = 2.0 * PI * season / (self.config.food_season_length as f32);
+        let density = 1.0 - self.config.food_season_amplitude * (1.0 - phase.cos()) / 2.0;
+        let active_foods = ((self.world.foods.len() as f32) * density).round() as usize;
+
+        for (food_id, food) in self.world.foods.iter_mut().enumerate() {
+            food.dormant = food_id >= active_foods;
+        }
+    }
+
+    /// Continuous evolution: animals that are too old or have starved (or
+    /// have been eaten) are removed, while the ones that have eaten enough
+    /// give birth to a child bred with a random partner of the same species.
+    fn process_births_and_deaths(&mut self, rng: &mut dyn RngCore) {
+        let config = &self.config;
+        let observers = &mut self.observers;
+
+        self.world.animals.retain(|animal| {
+            let retained = animal.is_alive() && animal.lifetime < config.sim_max_age;
+
+            // Animals that have starved or have been hunted down were
+            // already reported by `report_deaths()`
+            if !retained && animal.is_alive() {
+                for observer in observers.iter_mut() {
+                    observer.on_animal_died(animal);
+                }
+            }
+
+            retained
+        });
+
+        let survivors = self.world.animals.len();
+
+        let ga = self.genetic_algorithm();
+        let mut newborns = Vec::new();
+
+        for id in 0..self.world.animals.len() {
+            if self.world.animals.len() + newborns.len() >= self.config.sim_max_animals {
+                break;
+            }
+
+            let parent = &self.world.animals[id];
+
+            if parent.unspent_satiation < self.config.sim_birth_satiation {
+                continue;
+            }
+
+            let mates: Vec<_> = self
+                .world
+                .animals
+                .iter()
+                .enumerate()
+                .filter(|(mate_id, mate)| *mate_id != id && mate.species == parent.species)
+                .map(|(_, mate)| mate)
+                .collect();
+
+            // Breeding an animal with itself would just clone it
+            if mates.is_empty() {
+                continue;
+            }
+
+            let mate = mates[rng.gen_range(0..mates.len())];
+            let child = ga.breed(rng, &parent.as_chromosome(), &mate.as_chromosome());
+            let mut child = Animal::from_chromosome(&self.config, rng, parent.species, child);
+
+            child.position = parent.position;
+            child.trail = vec![parent.position];
+            newborns.push(child);
+
+            self.world.animals[id].unspent_satiation = 0;
+        }
+
+        self.world.animals.extend(newborns);
+
+        for &species in &Species::ALL {
+            let min_animals = self
+                .config
+                .sim_min_animals
+                .min(species.population(&self.config));
+
+            let animals = self
+                .world
+                .animals
+                .iter()
+                .filter(|animal| animal.species == species)
+                .count();
+
+            for _ in animals..min_animals {
+                let animal = Animal::random(&self.config, rng, species);
+                self.world.animals.push(animal);
+            }
+        }
+
+        for animal in &self.world.animals[survivors..] {
+            for observer in &mut self.observers {
+                observer.on_animal_born(animal);
+            }
+        }
     }
 
     fn try_evolving(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
         self.age += 1;
 
         if self.age > self.config.sim_generation_length {
-            Some(self.evolve(rng))
+            if self.config.sim_continuous {
+                Some(self.summarize(rng))
+            } else {
+                Some(self.evolve(rng))
+            }
         } else {
             None
         }
     }
 
+    /// Continuous evolution has no generations per se, so instead of evolving
+    /// we just report how the current population is doing (and bring back
+    /// the finite food that has been eaten).
+    fn summarize(&mut self, rng: &mut dyn RngCore) -> Statistics {
+        self.age = 0;
+        self.generation += 1;
+
+        for food in &mut self.world.foods {
+            if food.depleted {
+                food.replenish(&self.config, &*self.food_spawner, rng);
+            }
+        }
+
+        self.champions.clear();
+
+        let mut statistics = |species| {
+            let individuals = self.individuals(species);
+
+            if individuals.is_empty() {
+                None
+            } else {
+                self.record_champions(species, &individuals);
+                Some(ga::Statistics::new(&individuals))
+            }
+        };
+
+        let prey_statistics = statistics(Species::Prey).expect("all the prey has died out");
+        let predators_statistics = statistics(Species::Predator);
+
+        Statistics {
+            generation: self.generation - 1,
+            ga: prey_statistics,
+            predators: predators_statistics,
+        }
+    }
+
     fn evolve(&mut self, rng: &mut dyn RngCore) -> Statistics {
         self.age = 0;
         self.generation += 1;
+        self.champions.clear();
+
+        let (mut animals, statistics) = self.evolve_species(rng, Species::Prey);
+
+        let predators_statistics = if self.config.world_predators > 0 {
+            let (predators, statistics) = self.evolve_species(rng, Species::Predator);
+            animals.extend(predators);
+            Some(statistics)
+        } else {
+            None
+        };
+
+        self.world.animals = animals;
+        self.food_spawner.reset(rng);
+
+        for food in &mut self.world.foods {
+            food.replenish(&self.config, &*self.food_spawner, rng);
+        }
+
+        Statistics {
+            generation: self.generation - 1,
+            ga: statistics,
+            predators: predators_statistics,
+        }
+    }
 
-        let mut individuals: Vec<_> = self
+    /// Each species has its own population and its own genetic algorithm, so
+    /// that predators and prey can co-evolve.
+    fn evolve_species(
+        &mut self,
+        rng: &mut dyn RngCore,
+        species: Species,
+    ) -> (Vec<Animal>, ga::Statistics) {
+        let mut individuals = self.individuals(species);
+
+        if self.config.ga.reverse {
+            let max_fitness = individuals
+                .iter()
+                .map(|individual| individual.fitness)
+                .fold(0.0, f32::max);
+
+            for individual in &mut individuals {
+                individual.fitness = max_fitness - individual.fitness;
+            }
+        }
+
+        self.record_champions(species, &individuals);
+
+        let (individuals, statistics) = match &self.config.evolution {
+            EvolutionConfig::Generational => self.genetic_algorithm().evolve(rng, &individuals),
+
+            EvolutionConfig::MapElites { .. } => {
+                let children = self.evolve_map_elites(rng, species, &individuals);
+                (children, ga::Statistics::new(&individuals))
+            }
+
+            EvolutionConfig::Nsga2 { .. } => {
+                self.evaluate_objectives(species, &mut individuals);
+                self.optimizer(species).evolve(rng, &individuals)
+            }
+
+            EvolutionConfig::MuLambda { .. }
+            | EvolutionConfig::MuPlusLambda { .. }
+            | EvolutionConfig::Cmaes { .. } => self.optimizer(species).evolve(rng, &individuals),
+        };
+
+        let animals = individuals
+            .into_iter()
+            .map(|i| i.into_animal(&self.config, rng, species))
+            .collect();
+
+        (animals, statistics)
+    }
+
+    fn evolve_map_elites(
+        &mut self,
+        rng: &mut dyn RngCore,
+        species: Species,
+        individuals: &[AnimalIndividual],
+    ) -> Vec<AnimalIndividual> {
+        let dimensions = match &self.config.evolution {
+            EvolutionConfig::MapElites { dimensions } => dimensions,
+            _ => unreachable!(),
+        };
+
+        let ga_config = &self.config.ga;
+
+        let map_elites = self.map_elites.entry(species).or_insert_with(|| {
+            ga::MapElites::new(
+                dimensions.iter().map(|dimension| dimension.cells).collect(),
+                ga_config.crossover.clone(),
+                ga_config.mutation.clone(),
+            )
+        });
+
+        let animals = self
             .world
             .animals
             .iter()
-            .map(AnimalIndividual::from_animal)
-            .collect();
+            .filter(|animal| animal.species == species);
 
-        if self.config.ga_reverse == 1 {
-            let max_satiation = self
-                .world
-                .animals
+        for (animal, individual) in animals.zip(individuals) {
+            let behavior: Vec<_> = dimensions
                 .iter()
-                .map(|animal| animal.satiation)
-                .max()
-                .unwrap_or_default();
+                .map(|dimension| dimension.normalize(animal))
+                .collect();
 
-            for individual in &mut individuals {
-                individual.fitness = (max_satiation as f32) - individual.fitness;
+            map_elites.insert(individual, &behavior);
+        }
+
+        (0..individuals.len())
+            .map(|_| map_elites.breed(rng).expect("archive is empty"))
+            .collect()
+    }
+
+    fn evaluate_objectives(&mut self, species: Species, individuals: &mut [AnimalIndividual]) {
+        let animals: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .filter(|animal| animal.species == species)
+            .collect();
+
+        for objective in &mut self.objectives {
+            let scores = objective.evaluate(&self.config, &animals);
+
+            for (individual, score) in individuals.iter_mut().zip(scores) {
+                individual.objectives.push(score);
             }
         }
+    }
 
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection::default(),
-            ga::UniformCrossover::default(),
-            ga::GaussianMutation::new(self.config.ga_mut_chance, self.config.ga_mut_coeff),
-        );
+    fn record_champions(&mut self, species: Species, individuals: &[AnimalIndividual]) {
+        let mut individuals: Vec<_> = individuals.iter().collect();
 
-        let (individuals, statistics) = ga.evolve(rng, &individuals);
+        individuals.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap_or(Ordering::Equal));
 
-        self.world.animals = individuals
-            .into_iter()
-            .map(|i| i.into_animal(&self.config, rng))
+        let champions = individuals.into_iter().map(|individual| AnimalCheckpoint {
+            species,
+            genes: individual.chromosome.iter().copied().collect(),
+        });
+
+        self.champions.extend(champions);
+    }
+
+    fn individuals(&mut self, species: Species) -> Vec<AnimalIndividual> {
+        let animals: Vec<_> = self
+            .world
+            .animals
+            .iter()
+            .filter(|animal| animal.species == species)
             .collect();
 
-        for food in &mut self.world.foods {
-            food.position = rng.gen();
+        let fitnesses = self.fitness_function.evaluate(&self.config, &animals);
+
+        animals
+            .into_iter()
+            .zip(fitnesses)
+            .map(|(animal, fitness)| AnimalIndividual::from_animal(animal, fitness))
+            .collect()
+    }
+
+    /// Returns the optimizer evolving given species, creating it on first
+    /// use - optimizers keep their state (e.g. the parents) between
+    /// generations.
+    fn optimizer(
+        &mut self,
+        species: Species,
+    ) -> &mut Box<dyn ga::Optimizer<AnimalIndividual> + Send> {
+        let config = &self.config;
+
+        self.optimizers
+            .entry(species)
+            .or_insert_with(|| config.evolution.optimizer(&config.ga).unwrap())
+    }
+
+    fn genetic_algorithm(&self) -> ga::GeneticAlgorithm {
+        let mut ga = ga::GeneticAlgorithm::new(
+            self.config.ga.selection.clone(),
+            self.config.ga.crossover.clone(),
+            self.config.ga.mutation.clone(),
+        )
+        .with_crossover_chance(self.config.ga.crossover_chance);
+
+        for extra in &self.config.ga.extra_mutations {
+            ga = ga.with_mutation(extra.chance, extra.mutation.clone());
         }
 
-        Statistics {
-            generation: self.generation - 1,
-            ga: statistics,
+        match &self.config.ga.speciation {
+            Some(speciation) => ga.with_speciation(ga::Speciation::new(speciation.threshold)),
+            None => ga,
         }
     }
 }
@@ -150,20 +837,514 @@ impl Simulation {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::SeedableRng;
-    use rand_chacha::ChaCha8Rng;
 
     #[test]
     #[ignore]
     fn test() {
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
-        let mut sim = Simulation::random(Default::default(), &mut rng);
+        let mut sim = Simulation::new(Default::default(), 0);
+
+        let avg_fitness = (0..10).map(|_| sim.train().ga.avg_fitness()).sum::<f32>() / 10.0;
+
+        approx::assert_relative_eq!(20.002724, avg_fitness);
+    }
+
+    mod process_seasons {
+        use super::*;
+
+        #[test]
+        fn makes_food_dormant_in_the_middle_of_each_season() {
+            let config = Config {
+                world_foods: 10,
+                food_season_length: 100,
+                food_season_amplitude: 0.5,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+
+            let available = |sim: &Simulation| {
+                sim.world
+                    .foods
+                    .iter()
+                    .filter(|food| food.is_available())
+                    .count()
+            };
+
+            for &(age, expected) in &[(0, 10), (50, 5), (100, 10)] {
+                sim.age = age;
+                sim.process_seasons();
+
+                assert_eq!(available(&sim), expected, "age: {}", age);
+            }
+        }
+    }
+
+    mod process_births_and_deaths {
+        use super::*;
+
+        #[test]
+        fn animals_dont_breed_with_themselves() {
+            let config = Config {
+                world_animals: 2,
+                sim_continuous: true,
+                sim_min_animals: 1,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+
+            sim.world.animals[0].unspent_satiation = 10;
+            sim.world.animals[1].die();
+            sim.with_rng(|sim, rng| sim.process_births_and_deaths(rng));
+
+            assert_eq!(sim.world.animals.len(), 1);
+            assert_eq!(sim.world.animals[0].unspent_satiation, 10);
+
+            sim.world.animals.push(Animal::random(
+                &sim.config,
+                &mut ChaCha8Rng::from_seed(Default::default()),
+                Species::Prey,
+            ));
+
+            sim.with_rng(|sim, rng| sim.process_births_and_deaths(rng));
+
+            assert_eq!(sim.world.animals.len(), 3);
+            assert_eq!(sim.world.animals[0].unspent_satiation, 0);
+        }
+    }
+
+    mod step {
+        use super::*;
+
+        #[test]
+        fn is_deterministic() {
+            let config = Config {
+                world_predators: 5,
+                ..Default::default()
+            };
+
+            let positions = |seed| {
+                let mut sim = Simulation::new(config.clone(), seed);
+
+                for _ in 0..100 {
+                    sim.step();
+                }
+
+                sim.world
+                    .animals
+                    .iter()
+                    .map(|animal| animal.position)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(positions(1), positions(1));
+            assert_ne!(positions(1), positions(2));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    mod process_brains {
+        use super::*;
+
+        #[test]
+        fn gives_same_results_in_parallel() {
+            let config = Config {
+                world_predators: 5,
+                ..Default::default()
+            };
+
+            let mut sequential = Simulation::new(config.clone(), 0);
+            let mut parallel = Simulation::new(config, 0);
+
+            for _ in 0..100 {
+                sequential.process_brains_sequentially();
+                sequential.process_movements();
+
+                parallel.process_brains_in_parallel();
+                parallel.process_movements();
+            }
+
+            let animals = |sim: &Simulation| {
+                sim.world
+                    .animals
+                    .iter()
+                    .map(|animal| (animal.position, animal.rotation, animal.speed))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(animals(&sequential), animals(&parallel));
+        }
+    }
+
+    mod observers {
+        use super::*;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Debug, Default)]
+        struct Events {
+            steps_started: usize,
+            steps_ended: usize,
+            foods_eaten: usize,
+            animals_died: usize,
+            animals_born: usize,
+            generations: Vec<usize>,
+        }
+
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Events>>);
+
+        impl Observer for Recorder {
+            fn on_step_start(&mut self, _world: &World) {
+                self.0.lock().unwrap().steps_started += 1;
+            }
+
+            fn on_step_end(&mut self, _world: &World) {
+                self.0.lock().unwrap().steps_ended += 1;
+            }
+
+            fn on_food_eaten(&mut self, animal: &Animal, _food: &Food) {
+                assert_eq!(animal.species(), Species::Prey);
+                self.0.lock().unwrap().foods_eaten += 1;
+            }
+
+            fn on_animal_died(&mut self, _animal: &Animal) {
+                self.0.lock().unwrap().animals_died += 1;
+            }
+
+            fn on_animal_born(&mut self, _animal: &Animal) {
+                self.0.lock().unwrap().animals_born += 1;
+            }
+
+            fn on_generation_evolved(&mut self, statistics: &Statistics) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .generations
+                    .push(statistics.generation);
+            }
+        }
+
+        #[test]
+        fn are_notified_about_food_and_generations() {
+            let config = Config {
+                world_animals: 10,
+                sim_generation_length: 100,
+                ..Default::default()
+            };
+
+            let recorder = Recorder::default();
+            let mut sim = Simulation::new(config, 0);
+
+            sim.add_observer(recorder.clone());
+
+            // Steps up to the last one before evolution, which replaces all
+            // the animals (and so resets their satiation)
+            for _ in 0..100 {
+                assert!(sim.step().is_none());
+            }
+
+            let satiation: usize = sim.world.animals.iter().map(|a| a.satiation).sum();
+
+            assert!(satiation > 0);
+            assert_eq!(recorder.0.lock().unwrap().foods_eaten, satiation);
+
+            sim.train();
+            sim.train();
+
+            let events = recorder.0.lock().unwrap().clone();
+
+            assert_eq!(events.steps_started, 100 + 1 + 101);
+            assert_eq!(events.steps_ended, events.steps_started);
+            assert_eq!(events.animals_born, 0);
+            assert_eq!(events.generations, vec![0, 1]);
+        }
+
+        #[test]
+        fn are_notified_about_births_and_deaths() {
+            let config = Config {
+                world_animals: 10,
+                sim_generation_length: 100,
+                sim_continuous: true,
+                sim_max_age: 30,
+                ..Default::default()
+            };
+
+            let recorder = Recorder::default();
+            let mut sim = Simulation::new(config, 0);
+
+            sim.add_observer(recorder.clone());
+            sim.train();
+
+            let events = recorder.0.lock().unwrap().clone();
+
+            assert!(events.animals_died > 0);
+            assert_eq!(
+                sim.world.animals.len(),
+                10 + events.animals_born - events.animals_died
+            );
+            assert_eq!(events.generations, vec![0]);
+        }
+    }
+
+    mod checkpoint {
+        use super::*;
+
+        #[test]
+        fn restores_population() {
+            let config = Config {
+                world_animals: 3,
+                world_predators: 1,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+            sim.generation = 12;
+
+            let checkpoint = sim.checkpoint();
+            let restored = Simulation::from_checkpoint(checkpoint.clone(), 1);
+
+            assert_eq!(restored.generation(), 12);
+            assert_eq!(restored.world.animals.len(), 4);
+
+            for (animal, expected) in restored.world.animals.iter().zip(&checkpoint.animals) {
+                let actual = AnimalCheckpoint::from(animal);
+
+                assert_eq!(actual.species, expected.species);
+                approx::assert_relative_eq!(actual.genes.as_slice(), expected.genes.as_slice());
+            }
+        }
+
+        #[test]
+        fn rejects_genes_that_dont_fit_brains() {
+            let mut checkpoint = Simulation::new(Default::default(), 0).checkpoint();
+
+            checkpoint.animals[1].genes.pop();
+
+            let err = Simulation::try_from_checkpoint(checkpoint, 0)
+                .err()
+                .unwrap();
+
+            assert_eq!(err.fields.len(), 1);
+            assert_eq!(err.fields[0].field, "animals[1].genes");
+        }
+
+        #[test]
+        fn rejects_evolution_with_state() {
+            let config = Config {
+                evolution: EvolutionConfig::Cmaes { sigma: 0.1 },
+                ..Default::default()
+            };
+
+            let checkpoint = Simulation::new(config, 0).checkpoint();
+            let err = Simulation::try_from_checkpoint(checkpoint, 0)
+                .err()
+                .unwrap();
+
+            assert_eq!(err.fields.len(), 1);
+            assert_eq!(err.fields[0].field, "config.evolution.type");
+        }
+    }
+
+    mod map_elites {
+        use super::*;
+
+        #[test]
+        fn fills_archive_and_spawns_elites() {
+            let config = Config {
+                world_animals: 10,
+                sim_generation_length: 100,
+                evolution: EvolutionConfig::MapElites {
+                    dimensions: vec![
+                        DimensionConfig {
+                            measure: AnimalMeasure::AverageSpeed,
+                            min: 0.0,
+                            max: 0.005,
+                            cells: 4,
+                        },
+                        DimensionConfig {
+                            measure: AnimalMeasure::TurningRate,
+                            min: 0.0,
+                            max: 0.5,
+                            cells: 4,
+                        },
+                    ],
+                },
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+
+            assert_eq!(sim.elites(Species::Prey).count(), 0);
+            assert!(!sim.spawn_elite(Species::Prey, &[0, 0]));
+
+            sim.train();
+            sim.train();
+
+            assert_eq!(sim.world.animals.len(), 10);
+
+            let cell = sim
+                .elites(Species::Prey)
+                .next()
+                .expect("archive is empty")
+                .cell()
+                .to_vec();
+
+            assert!(sim.spawn_elite(Species::Prey, &cell));
+            assert_eq!(sim.world.animals.len(), 10);
+        }
+    }
+
+    mod speciation {
+        use super::*;
+
+        #[test]
+        fn reports_species() {
+            let config = Config {
+                world_animals: 10,
+                sim_generation_length: 100,
+                ga: GaConfig {
+                    speciation: Some(SpeciationConfig { threshold: 0.01 }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+            let statistics = sim.train();
+
+            // Random brains are all far apart from each other
+            assert_eq!(statistics.ga.species(), 10);
+            assert_eq!(sim.world.animals.len(), 10);
+        }
+    }
+
+    mod evolution_strategies {
+        use super::*;
+        use std::sync::{Arc, Mutex};
+
+        #[test]
+        fn evolve_population() {
+            for evolution in &[
+                EvolutionConfig::MuLambda { mu: 3, sigma: 0.1 },
+                EvolutionConfig::MuPlusLambda { mu: 3, sigma: 0.1 },
+                EvolutionConfig::Cmaes { sigma: 0.1 },
+            ] {
+                let config = Config {
+                    world_animals: 10,
+                    world_predators: 2,
+                    sim_generation_length: 100,
+                    evolution: evolution.clone(),
+                    ..Default::default()
+                };
+
+                let mut sim = Simulation::new(config, 0);
+
+                sim.train();
+                sim.train();
+
+                assert_eq!(sim.world.animals.len(), 12);
+            }
+        }
 
-        let avg_fitness = (0..10)
-            .map(|_| sim.train(&mut rng).ga.avg_fitness())
-            .sum::<f32>()
-            / 10.0;
+        #[test]
+        fn keep_optimizers_between_generations() {
+            /// Remembers CMA-ES' step size after each generation.
+            struct Spy {
+                cmaes: ga::Cmaes,
+                sigmas: Arc<Mutex<Vec<f32>>>,
+            }
+
+            impl ga::Optimizer<AnimalIndividual> for Spy {
+                fn evolve(
+                    &mut self,
+                    rng: &mut dyn RngCore,
+                    population: &[AnimalIndividual],
+                ) -> (Vec<AnimalIndividual>, ga::Statistics) {
+                    let result = self.cmaes.evolve(rng, population);
+
+                    self.sigmas.lock().unwrap().push(self.cmaes.sigma());
+                    result
+                }
+            }
+
+            let config = Config {
+                world_animals: 10,
+                sim_generation_length: 100,
+                evolution: EvolutionConfig::Cmaes { sigma: 0.1 },
+                ..Default::default()
+            };
+
+            let sigmas = Arc::new(Mutex::new(Vec::new()));
+            let mut sim = Simulation::new(config, 0);
+
+            sim.optimizers.insert(
+                Species::Prey,
+                Box::new(Spy {
+                    cmaes: ga::Cmaes::new(0.1),
+                    sigmas: Arc::clone(&sigmas),
+                }),
+            );
+
+            sim.train();
+            sim.train();
+
+            let sigmas = sigmas.lock().unwrap();
+
+            // The first generation only initializes the distribution, while
+            // the second one (reaching the same optimizer) adapts it
+            assert_eq!(sigmas.len(), 2);
+            approx::assert_relative_eq!(sigmas[0], 0.1);
+            approx::assert_relative_ne!(sigmas[1], 0.1);
+        }
+    }
 
-        approx::assert_relative_eq!(31.944998, avg_fitness);
+    mod nsga2 {
+        use super::*;
+
+        #[test]
+        fn evolves_population() {
+            let config = Config {
+                world_animals: 10,
+                world_predators: 2,
+                sim_generation_length: 100,
+                evolution: EvolutionConfig::Nsga2 {
+                    objectives: vec![FitnessConfig::Nutrition, FitnessConfig::Energy],
+                },
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+
+            sim.train();
+            sim.train();
+
+            assert_eq!(sim.generation(), 2);
+            assert_eq!(sim.world.animals.len(), 12);
+        }
+    }
+
+    mod process_hunting {
+        use super::*;
+
+        #[test]
+        fn predators_eat_prey_within_reach() {
+            let config = Config {
+                world_animals: 2,
+                world_predators: 1,
+                ..Default::default()
+            };
+
+            let mut sim = Simulation::new(config, 0);
+
+            sim.world.animals[0].position = na::Point2::new(0.5, 0.5);
+            sim.world.animals[1].position = na::Point2::new(0.9, 0.9);
+            sim.world.animals[2].position = na::Point2::new(0.505, 0.5);
+            sim.process_hunting();
+
+            assert!(!sim.world.animals[0].is_alive());
+            assert!(sim.world.animals[1].is_alive());
+            assert_eq!(sim.world.animals[2].species(), Species::Predator);
+            assert_eq!(sim.world.animals[2].satiation, 1);
+        }
     }
 }
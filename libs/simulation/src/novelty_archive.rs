@@ -0,0 +1,131 @@
+use crate::*;
+use std::collections::VecDeque;
+
+/// Behaviors seen in the previous generations - novelty search rewards
+/// animals for behaving differently both from each other and from them.
+#[derive(Clone, Debug)]
+pub struct NoveltyArchive {
+    behaviors: VecDeque<Vec<f32>>,
+    capacity: usize,
+}
+
+impl NoveltyArchive {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            behaviors: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviors.is_empty()
+    }
+
+    pub fn behaviors(&self) -> impl Iterator<Item = &[f32]> {
+        self.behaviors.iter().map(|behavior| behavior.as_slice())
+    }
+
+    /// Scores each of given behaviors with its average distance to its `k`
+    /// nearest neighbours, looking both at the other behaviors and at the
+    /// archive.
+    pub fn novelty(&self, behaviors: &[Vec<f32>], k: usize) -> Vec<f32> {
+        behaviors
+            .iter()
+            .enumerate()
+            .map(|(id, behavior)| {
+                let others = behaviors
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_id, _)| *other_id != id)
+                    .map(|(_, other)| other);
+
+                let mut distances: Vec<_> = others
+                    .chain(&self.behaviors)
+                    .map(|other| distance(behavior, other))
+                    .collect();
+
+                distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                distances.truncate(k);
+
+                if distances.is_empty() {
+                    0.0
+                } else {
+                    distances.iter().sum::<f32>() / distances.len() as f32
+                }
+            })
+            .collect()
+    }
+
+    /// Remembers given behavior, forgetting the oldest one if the archive is
+    /// already full.
+    pub fn insert(&mut self, behavior: Vec<f32>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.behaviors.len() == self.capacity {
+            self.behaviors.pop_front();
+        }
+
+        self.behaviors.push_back(behavior);
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod novelty {
+        use super::*;
+
+        #[test]
+        fn compares_behaviors_with_each_other() {
+            let archive = NoveltyArchive::new(10);
+            let behaviors = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![0.0, 4.0]];
+
+            let actual = archive.novelty(&behaviors, 1);
+
+            approx::assert_relative_eq!(actual.as_slice(), [1.0, 1.0, 3.0].as_ref());
+        }
+
+        #[test]
+        fn compares_behaviors_with_the_archive() {
+            let mut archive = NoveltyArchive::new(10);
+            archive.insert(vec![0.0, 3.5]);
+
+            let behaviors = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![0.0, 4.0]];
+            let actual = archive.novelty(&behaviors, 2);
+
+            approx::assert_relative_eq!(actual.as_slice(), [2.25, 1.75, 1.75].as_ref());
+        }
+    }
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn forgets_the_oldest_behaviors() {
+            let mut archive = NoveltyArchive::new(2);
+
+            archive.insert(vec![1.0]);
+            archive.insert(vec![2.0]);
+            archive.insert(vec![3.0]);
+
+            let behaviors: Vec<_> = archive.behaviors().collect();
+
+            assert_eq!(behaviors, [[2.0].as_ref(), [3.0].as_ref()]);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+/// Gets notified about what's happening inside the simulation - useful for
+/// logging, collecting metrics or driving visualizations.
+///
+/// All the hooks do nothing by default, so observers only have to implement
+/// the ones they care about.
+pub trait Observer: Send {
+    /// Called at the beginning of each step, before anything moves.
+    fn on_step_start(&mut self, _world: &World) {
+        //
+    }
+
+    /// Called at the end of each step (after evolution, if the step has
+    /// finished a generation).
+    fn on_step_end(&mut self, _world: &World) {
+        //
+    }
+
+    /// Called when given prey eats given food, before the food regrows.
+    fn on_food_eaten(&mut self, _animal: &Animal, _food: &Food) {
+        //
+    }
+
+    /// Called when given animal starves, gets hunted down or - in continuous
+    /// evolution - dies of old age.
+    fn on_animal_died(&mut self, _animal: &Animal) {
+        //
+    }
+
+    /// Called when given animal appears in the world during continuous
+    /// evolution (populations created by generational evolution are not
+    /// reported one by one - see `on_generation_evolved()`).
+    fn on_animal_born(&mut self, _animal: &Animal) {
+        //
+    }
+
+    /// Called after each generation, with the same statistics that
+    /// `Simulation::step()` returns.
+    fn on_generation_evolved(&mut self, _statistics: &Statistics) {
+        //
+    }
+}
@@ -0,0 +1,33 @@
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Species {
+    /// Eats food and tries not to get eaten
+    Prey,
+
+    /// Hunts prey
+    Predator,
+}
+
+impl Species {
+    crate const ALL: [Self; 2] = [Self::Prey, Self::Predator];
+
+    /// Initial population of this species.
+    crate fn population(self, config: &Config) -> usize {
+        match self {
+            Self::Prey => config.world_animals,
+            Self::Predator => config.world_predators,
+        }
+    }
+
+    /// Number of things animals of this species can see (e.g. each kind of
+    /// food is a separate thing); each of them is perceived through its own
+    /// set of `eye_cells` photoreceptors.
+    crate fn eye_channels(self, config: &Config) -> usize {
+        match self {
+            Self::Prey => config.food_kinds.len() + (config.world_predators > 0) as usize,
+            Self::Predator => 1,
+        }
+    }
+}
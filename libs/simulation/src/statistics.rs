@@ -1,22 +1,79 @@
 use crate::*;
 use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Statistics {
     pub generation: usize,
+    #[serde(serialize_with = "serialize_ga")]
     pub ga: ga::Statistics,
+    #[serde(serialize_with = "serialize_predators")]
+    pub predators: Option<ga::Statistics>,
+}
+
+/// Serializable counterpart of `ga::Statistics` (the genetic algorithm
+/// itself doesn't depend on serde).
+#[derive(Serialize)]
+struct GaStatistics {
+    min_fitness: f32,
+    max_fitness: f32,
+    avg_fitness: f32,
+    median_fitness: f32,
+    species: usize,
+}
+
+impl From<&ga::Statistics> for GaStatistics {
+    fn from(stats: &ga::Statistics) -> Self {
+        Self {
+            min_fitness: stats.min_fitness(),
+            max_fitness: stats.max_fitness(),
+            avg_fitness: stats.avg_fitness(),
+            median_fitness: stats.median_fitness(),
+            species: stats.species(),
+        }
+    }
+}
+
+fn serialize_ga<S>(stats: &ga::Statistics, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    GaStatistics::from(stats).serialize(serializer)
+}
+
+fn serialize_predators<S>(stats: &Option<ga::Statistics>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    stats.as_ref().map(GaStatistics::from).serialize(serializer)
 }
 
 impl fmt::Display for Statistics {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "generation {}:", self.generation)?;
-        write!(
-            f,
-            "min[{:.2}] max[{:.2}] avg[{:.2}] median[{:.2}]",
-            self.ga.min_fitness(),
-            self.ga.max_fitness(),
-            self.ga.avg_fitness(),
-            self.ga.median_fitness()
-        )
+        write_ga_statistics(f, &self.ga)?;
+
+        if let Some(predators) = &self.predators {
+            write!(f, "\npredators: ")?;
+            write_ga_statistics(f, predators)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_ga_statistics(f: &mut fmt::Formatter<'_>, stats: &ga::Statistics) -> fmt::Result {
+    write!(
+        f,
+        "min[{:.2}] max[{:.2}] avg[{:.2}] median[{:.2}]",
+        stats.min_fitness(),
+        stats.max_fitness(),
+        stats.avg_fitness(),
+        stats.median_fitness()
+    )?;
+
+    if stats.species() > 1 {
+        write!(f, " species[{}]", stats.species())?;
     }
+
+    Ok(())
 }
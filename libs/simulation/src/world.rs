@@ -17,13 +17,39 @@ impl World {
 }
 
 impl World {
-    crate fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
-        let animals = (0..config.world_animals)
-            .map(|_| Animal::random(config, rng))
-            .collect();
+    crate fn random(
+        config: &Config,
+        food_spawner: &dyn FoodSpawner,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let mut animals = Vec::new();
+
+        for &species in &Species::ALL {
+            animals.extend(
+                (0..species.population(config)).map(|_| Animal::random(config, rng, species)),
+            );
+        }
 
-        let foods = (0..config.world_foods).map(|_| Food::random(rng)).collect();
+        let foods = (0..config.world_foods)
+            .map(|_| Food::random(config, food_spawner, rng))
+            .collect();
 
         Self { animals, foods }
     }
+
+    /// Positions of all the foods of given kind that can be currently eaten.
+    crate fn food_positions(&self, kind: usize) -> impl Iterator<Item = na::Point2<f32>> + '_ {
+        self.foods
+            .iter()
+            .filter(move |food| food.kind == kind && food.is_available())
+            .map(|food| food.position)
+    }
+
+    /// Positions of all the living animals of given species.
+    crate fn positions(&self, species: Species) -> impl Iterator<Item = na::Point2<f32>> + '_ {
+        self.animals
+            .iter()
+            .filter(move |animal| animal.species == species && animal.is_alive())
+            .map(|animal| animal.position)
+    }
 }
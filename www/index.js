@@ -60,10 +60,20 @@ terminal.println("");
 terminal.println(`  * p / photoreceptors (default=${config.eye_cells})`);
 terminal.println("    number of eye cells per each animal");
 terminal.println("");
+terminal.println(`  * preset (default=default)`);
+terminal.println(`    one of: ${sim.Simulation.presets().join(", ")};`);
+terminal.println("    other parameters are applied on top of it");
+terminal.println("");
+terminal.println(`  * s / seed (current=${simulation.seed()})`);
+terminal.println("    seed for the random number generator; the same seed");
+terminal.println("    and parameters always yield the same simulation");
+terminal.println("");
 terminal.println("  Examples:");
 terminal.println("    reset animals=100 foods=100");
 terminal.println("    r a=100 f=100");
 terminal.println("    r p=3");
+terminal.println("    r s=1234");
+terminal.println("    r preset=predator-prey a=20");
 terminal.println("");
 terminal.println("- (t)rain [how-many-generations]");
 terminal.println("  Fast-forwards one or many generations, allowing to");
@@ -73,12 +83,22 @@ terminal.println("  Examples:");
 terminal.println("    train");
 terminal.println("    t 5");
 terminal.println("");
+terminal.println("- (h)istory [csv|json]");
+terminal.println("  Downloads statistics of all the generations so far,");
+terminal.println("  for plotting learning curves (default=csv).");
+terminal.println("");
+terminal.println("  Examples:");
+terminal.println("    history");
+terminal.println("    h json");
+terminal.println("");
 terminal.println("---- Advanced Tips™ ----");
 terminal.println("");
 terminal.println("- `reset` can modify *all* of the parameters:");
 terminal.println("");
 terminal.println("  * r i:integer_param=123 f:float_param=123");
+terminal.println("  * r s:string_param=abc b:bool_param=true");
 terminal.println("  * r a=200 f=200 f:food_size=0.002");
+terminal.println("  * r s:ga.selection.type=tournament i:ga.selection.size=3");
 terminal.println("");
 terminal.println("  This is considered advanced, because you'll have");
 terminal.println("  to look into the source code to find the names.");
@@ -86,9 +106,33 @@ terminal.println("  (https://github.com/Patryk27/shorelark/blob/main/libs/simula
 terminal.println("");
 terminal.println("---- Funky scenarios ----");
 terminal.println("");
-terminal.println("  * r i:ga_reverse=1 f:sim_speed_min=0.003");
+terminal.println("  * r b:ga.reverse=true f:sim_speed_min=0.003");
 terminal.println("    (birdies *avoid* food)");
 terminal.println("");
+terminal.println("  * r s:food_spawner=clustered b:food_finite=true");
+terminal.println("    (scarce food growing in patches)");
+terminal.println("");
+terminal.println("  * r i:food_season_length=1000 f:food_speed=0.001");
+terminal.println("    (drifting food and harsh winters)");
+terminal.println("");
+terminal.println("  * r s:fitness.mode=distance");
+terminal.println("    (birdies rewarded for travelling, not eating)");
+terminal.println("");
+terminal.println("  * r s:fitness.mode=novelty s:fitness.behavior.type=cells i:fitness.behavior.resolution=8");
+terminal.println("    (birdies rewarded for exploring new places)");
+terminal.println("");
+terminal.println("  * r f:ga.speciation.threshold=0.3");
+terminal.println("    (birdies evolving in separate species)");
+terminal.println("");
+terminal.println("  * r s:evolution.type=cmaes f:evolution.sigma=0.1");
+terminal.println("    (evolution strategy instead of the genetic algorithm)");
+terminal.println("");
+terminal.println("  * r i:world_predators=5");
+terminal.println("    (predators hunting the birdies)");
+terminal.println("");
+terminal.println("  * r b:sim_continuous=true");
+terminal.println("    (births and deaths instead of generations)");
+terminal.println("");
 terminal.println("  * r i:brain_neurons=1");
 terminal.println("    (single-neuroned zombies)");
 terminal.println("");
@@ -136,6 +180,11 @@ function exec(input) {
         return;
     }
 
+    if (cmd === "h" || cmd === "history") {
+        execHistory(args);
+        return;
+    }
+
     throw "unknown command";
 }
 
@@ -148,35 +197,50 @@ function execPause(args) {
 }
 
 function execReset(args) {
-    let config = sim.Simulation.default_config();
+    let preset = "default";
+    let overrides = {};
+    let seed = undefined;
 
     for (const arg of args) {
         const [argName, argValue] = arg.split("=");
 
         if (argName.startsWith("i:")) {
-            config[argName.slice(2)] = parseInt(argValue);
+            setParam(overrides, argName.slice(2), parseInt(argValue));
         } else if (argName.startsWith("f:")) {
-            config[argName.slice(2)] = parseFloat(argValue);
+            setParam(overrides, argName.slice(2), parseFloat(argValue));
+        } else if (argName.startsWith("s:")) {
+            setParam(overrides, argName.slice(2), argValue);
+        } else if (argName.startsWith("b:")) {
+            setParam(overrides, argName.slice(2), argValue === "true");
         } else {
             switch (argName) {
                 case "a":
                 case "animals":
-                    config.world_animals = parseInt(argValue);
+                    overrides.world_animals = parseInt(argValue);
                     break;
 
                 case "f":
                 case "foods":
-                    config.world_foods = parseInt(argValue);
+                    overrides.world_foods = parseInt(argValue);
                     break;
 
                 case "n":
                 case "neurons":
-                    config.brain_neurons = parseInt(argValue);
+                    overrides.brain_neurons = parseInt(argValue);
                     break;
 
                 case "p":
                 case "photoreceptors":
-                    config.eye_cells = parseInt(argValue);
+                    overrides.eye_cells = parseInt(argValue);
+                    break;
+
+                case "s":
+                case "seed":
+                    seed = parseInt(argValue);
+                    break;
+
+                case "preset":
+                    preset = argValue;
                     break;
 
                 default:
@@ -185,7 +249,29 @@ function execReset(args) {
         }
     }
 
-    simulation = new sim.Simulation(config);
+    const config = sim.Simulation.preset_config(preset, overrides);
+
+    simulation.reset(config, seed);
+    terminal.println(`  seed: ${simulation.seed()}`);
+}
+
+/**
+ * Sets given config parameter; nested ones are separated with dots, e.g.
+ * `ga.selection.type`.
+ */
+function setParam(config, name, value) {
+    const path = name.split(".");
+    const key = path.pop();
+
+    for (const section of path) {
+        if (config[section] === undefined) {
+            config[section] = {};
+        }
+
+        config = config[section];
+    }
+
+    config[key] = value;
 }
 
 function execTrain(args) {
@@ -201,18 +287,55 @@ function execTrain(args) {
         }
 
         const stats = simulation.train();
-        terminal.println(stats);
+        terminal.println(stats.text);
+    }
+}
+
+function execHistory(args) {
+    if (args.length > 1) {
+        throw "this command accepts at most one parameter";
+    }
+
+    const format = args.length == 0 ? "csv" : args[0];
+    let history;
+
+    if (format === "csv") {
+        history = simulation.history_csv();
+    } else if (format === "json") {
+        history = simulation.history_json();
+    } else {
+        throw `unknown format: ${format}`;
     }
+
+    const link = document.createElement("a");
+
+    link.href = URL.createObjectURL(new Blob([history], { type: `text/${format}` }));
+    link.download = `shorelark-${simulation.seed()}.${format}`;
+    link.click();
+
+    URL.revokeObjectURL(link.href);
 }
 
 /* ---------- */
 
+function animalColor(animal) {
+    if (!animal.alive) {
+        return 'rgb(96, 96, 96)';
+    }
+
+    if (animal.species === 'predator') {
+        return 'rgb(255, 64, 64)';
+    }
+
+    return 'rgb(255, 255, 255)';
+}
+
 function redraw() {
     if (active) {
         const stats = simulation.step();
 
         if (stats) {
-            terminal.println(stats);
+            terminal.println(stats.text);
         }
     }
 
@@ -225,8 +348,8 @@ function redraw() {
         viewport.drawCircle(
             food.x,
             food.y,
-            (config.food_size / 2.0),
-            'rgb(0, 255, 128)',
+            (food.size / 2.0),
+            food.nutrition < 0.0 ? 'rgb(255, 0, 192)' : 'rgb(0, 255, 128)',
         );
     }
 
@@ -236,9 +359,13 @@ function redraw() {
             animal.y,
             config.food_size,
             animal.rotation,
-            'rgb(255, 255, 255)',
+            animalColor(animal),
         );
 
+        if (!animal.alive) {
+            continue;
+        }
+
         const anglePerCell = config.eye_fov_angle / config.eye_cells;
 
         for (let cellId = 0; cellId < config.eye_cells; cellId += 1) {